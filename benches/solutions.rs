@@ -0,0 +1,105 @@
+// Benchmarks every generator and solver on the real inputs in input/2025.
+// Each part gets its own group so that alternatives are compared side by
+// side, e.g. `day04/part2/main` against `day04/part2/Alternative`.
+//
+// Save a baseline and compare against it later with
+//
+//     cargo bench --bench solutions -- --save-baseline before
+//     cargo bench --bench solutions -- --baseline before
+use aoc_2025::prelude::*;
+use aoc_2025::runner::input_path;
+use aoc_2025::*;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// Input sizes, as a percentage of the real input.
+const SIZES: &[usize] = &[25, 50, 100];
+
+/// Days where every line is a record of its own, so the input can be cut
+/// short and still be valid. The others only run at full size.
+const LINE_DAYS: &[u32] = &[1, 3, 4, 7, 8, 10];
+
+fn sized_inputs(day: u32) -> Vec<(usize, String)> {
+    let Ok(input) = std::fs::read_to_string(input_path(day)) else {
+        eprintln!("day {day}: no input at {}, skipping", input_path(day));
+        return Vec::new();
+    };
+    let input = input.trim_end_matches('\n');
+
+    if !LINE_DAYS.contains(&day) {
+        return vec![(100, input.to_string())];
+    }
+
+    let lines: Vec<&str> = input.lines().collect();
+    SIZES
+        .iter()
+        .map(|&size| (size, lines[..max(1, lines.len() * size / 100)].join("\n")))
+        .collect()
+}
+
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let inputs = sized_inputs(S::DAY);
+    if inputs.is_empty() {
+        return;
+    }
+
+    let ctx = Context::<S::Params>::default();
+    let mut group = c.benchmark_group(format!("day{:02}/parse", S::DAY));
+    for (size, input) in &inputs {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("input_generator", format!("{size}%")), input, |b, input| {
+            b.iter(|| S::parse(black_box(input), ctx.params()))
+        });
+    }
+    group.finish();
+
+    let mut solvers: Vec<(u32, &str, DaySolver<S>)> = vec![(1, "main", S::part1)];
+    if let Some(part2) = S::part2() {
+        solvers.push((2, "main", part2));
+    }
+    solvers.extend(S::alternatives());
+
+    let parsed: Vec<(usize, S::Input)> = inputs
+        .iter()
+        .map(|(size, input)| (*size, S::parse(input, ctx.params()).expect("benchmark input should parse")))
+        .collect();
+
+    for part in 1..=2 {
+        if !solvers.iter().any(|(p, ..)| *p == part) {
+            continue;
+        }
+
+        let mut group = c.benchmark_group(format!("day{:02}/part{part}", S::DAY));
+        for (size, input) in &parsed {
+            for (_, name, solve) in solvers.iter().filter(|(p, ..)| *p == part) {
+                group.bench_with_input(BenchmarkId::new(*name, format!("{size}%")), input, |b, input| {
+                    b.iter(|| solve(black_box(input), &ctx))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+fn solutions(c: &mut Criterion) {
+    bench_day::<day01::Day01>(c);
+    bench_day::<day02::Day02>(c);
+    bench_day::<day03::Day03>(c);
+    bench_day::<day04::Day04>(c);
+    bench_day::<day05::Day05>(c);
+    bench_day::<day06::Day06>(c);
+    bench_day::<day07::Day07>(c);
+    bench_day::<day08::Day08>(c);
+    bench_day::<day09::Day09>(c);
+    bench_day::<day10::Day10>(c);
+    bench_day::<day11::Day11>(c);
+    bench_day::<day12::Day12>(c);
+}
+
+// Some days take a good fraction of a second, so keep the sample count down.
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = solutions
+}
+criterion_main!(benches);
\ No newline at end of file
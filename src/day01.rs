@@ -1,87 +1,252 @@
-#[derive(Debug)]
+use crate::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Dir {
     Left,
     Right,
 }
 
-#[aoc_generator(day1)]
-pub fn input_generator(input: &str) -> Vec<(Dir, isize, usize)> {
-    input
-        .lines()
-        .map(|line| {
-            let (l, r) = line.trim().split_at(1);
-            (
-                match l.chars().next() {
-                    Some('L') => Dir::Left,
-                    Some('R') => Dir::Right,
-                    _ => panic!("Invalid direction"),
-                },
-                r.parse::<isize>().unwrap() % 100,
-                r.parse::<usize>().unwrap() / 100,
-            )
-        })
-        .collect()
+impl FromField for Dir {
+    fn from_field(field: &str) -> Result<Self, String> {
+        match field {
+            "L" => Ok(Dir::Left),
+            "R" => Ok(Dir::Right),
+            _   => Err("`L` or `R`".to_string()),
+        }
+    }
 }
 
-#[aoc(day1, part1)]
-pub fn solve_part1(input: &Vec<(Dir, isize, usize)>) -> usize {
-    let mut dial = 50;
-    let mut count = 0;
-
-    for (dir, steps, _) in input {
-        match dir {
-            Dir::Left => dial -= steps,
-            Dir::Right => dial += steps,
-        }
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Numbers on the dial, from 0 up.
+    pub positions: u64,
+    /// Where the dial points before the first rotation.
+    pub start: u64,
+}
 
-        if dial > 99 {
-            dial -= 100;
-        } else if dial < 0 {
-            dial += 100;
-        }
+impl Default for Params {
+    fn default() -> Self {
+        Self { positions: 100, start: 50 }
+    }
+}
 
-        if dial == 0 {
-            count += 1;
+impl Params {
+    fn check(&self) -> Result<(), String> {
+        if self.positions == 0 {
+            return Err("at least one position".to_string());
         }
+
+        Ok(())
+    }
+}
+
+params!(Params { positions, start }, validate = Params::check);
+
+/// A dial numbered from 0 to `size - 1`, turned a click at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    size: u64,
+    position: u64,
+}
+
+/// What a single rotation did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn {
+    /// Whether the dial was left pointing at zero.
+    pub landed: bool,
+    /// Clicks that pointed the dial at zero, the last one included.
+    pub zeros: u64,
+    /// How many clicks in the first of those came, if there were any. The
+    /// rest follow a full turn apart.
+    pub first_zero: Option<u64>,
+}
+
+impl Dial {
+    pub fn new(size: u64, start: u64) -> Self {
+        assert!(size > 0, "a dial needs at least one position");
+        Self { size, position: start % size }
+    }
+
+    pub fn with_params(params: &Params) -> Self {
+        Self::new(params.positions, params.start)
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Turns the dial by `clicks` in constant time, however many times that
+    /// goes round.
+    pub fn rotate(&mut self, dir: Dir, clicks: u64) -> Turn {
+        let partial = clicks % self.size;
+
+        // Clicks until the dial next points at zero, a full turn if it
+        // already does. Every zero after that is another full turn on.
+        let to_zero = match dir {
+            Dir::Left  => self.position,
+            Dir::Right => self.size - self.position,
+        };
+        let to_zero = if to_zero == 0 { self.size } else { to_zero };
+        let zeros = if clicks >= to_zero { (clicks - to_zero) / self.size + 1 } else { 0 };
+
+        // Written so as not to overflow, whatever the size.
+        let position = match dir {
+            Dir::Left if partial <= self.position => self.position - partial,
+            Dir::Left                             => self.position + (self.size - partial),
+            Dir::Right if partial >= to_zero      => partial - to_zero,
+            Dir::Right                            => self.position + partial,
+        };
+
+        self.position = position;
+        Turn { landed: position == 0, zeros, first_zero: (zeros > 0).then_some(to_zero) }
+    }
+}
+
+/// One rotation's effect on the dial, see `trace`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// Which rotation in the input, from 0.
+    pub index: usize,
+    pub dir: Dir,
+    pub clicks: u64,
+    pub from: u64,
+    pub to: u64,
+    pub turn: Turn,
+    size: u64,
+}
+
+impl Step {
+    /// How many clicks into the rotation each zero came. There can be a
+    /// great many of these for a big rotation.
+    pub fn zero_clicks(&self) -> impl Iterator<Item = u64> + use<> {
+        let (size, zeros) = (self.size, self.turn.zeros);
+        self.turn.first_zero.into_iter().flat_map(move |first| (0..zeros).map(move |turn| first + turn * size))
+    }
+}
+
+/// Iterator over the rotations in order, with the dial before and after each.
+pub struct Trace<'a> {
+    dial: Dial,
+    rotations: std::iter::Enumerate<std::slice::Iter<'a, (Dir, u64)>>,
+}
+
+impl Iterator for Trace<'_> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        let (index, &(dir, clicks)) = self.rotations.next()?;
+        let from = self.dial.position();
+        let turn = self.dial.rotate(dir, clicks);
+
+        Some(Step { index, dir, clicks, from, to: self.dial.position(), turn, size: self.dial.size() })
+    }
+}
+
+pub fn trace<'a>(input: &'a [(Dir, u64)], params: &Params) -> Trace<'a> {
+    Trace { dial: Dial::with_params(params), rotations: input.iter().enumerate() }
+}
+
+/// How many rotations left the dial at each position, and which was first.
+pub fn histogram(steps: impl IntoIterator<Item = Step>) -> BTreeMap<u64, (usize, usize)> {
+    let mut counts = BTreeMap::new();
+    for step in steps {
+        counts.entry(step.to).or_insert((0, step.index)).0 += 1;
+    }
+
+    counts
+}
+
+/// The first rotation to leave the dial at `position`.
+pub fn first_landing(steps: impl IntoIterator<Item = Step>, position: u64) -> Option<usize> {
+    steps.into_iter().find(|step| step.to == position).map(|step| step.index)
+}
+
+/// Every rotation as a table, then every position rotations stopped at.
+pub fn trace_table(input: &[(Dir, u64)], params: &Params) -> String {
+    let mut table = String::new();
+    table.push_str("|     # | Rotation             |  From |    To | Zeros      | First zero |\n");
+    table.push_str("|------:|:---------------------|------:|------:|-----------:|-----------:|\n");
+
+    let steps: Vec<Step> = trace(input, params).collect();
+    for step in &steps {
+        let dir = if step.dir == Dir::Left { 'L' } else { 'R' };
+        let first_zero = step.turn.first_zero.map_or(String::new(), |click| click.to_string());
+        table.push_str(&format!(
+            "| {:>5} | {:<20} | {:>5} | {:>5} | {:>10} | {:>10} |\n",
+            step.index + 1, format!("{dir}{}", step.clicks), step.from, step.to, step.turn.zeros, first_zero
+        ));
+    }
+
+    table.push_str("\n| Position | Landings | First landing |\n");
+    table.push_str("|---------:|---------:|--------------:|\n");
+    for (position, (count, first)) in histogram(steps) {
+        table.push_str(&format!("| {position:>8} | {count:>8} | {:>13} |\n", first + 1));
     }
 
-    count
+    table
+}
+
+#[aoc_generator(day1)]
+pub fn input_generator(input: &str) -> Result<Vec<(Dir, u64)>, ParseError> {
+    parse_lines!(input.trim_end(), "{}{}" => Dir, u64).map_err(|err| err.on_day(1))
+}
+
+#[aoc(day1, part1)]
+pub fn solve_part1(input: &[(Dir, u64)]) -> usize {
+    solve_part1_with(input, &Params::default())
+}
+
+pub fn solve_part1_with(input: &[(Dir, u64)], params: &Params) -> usize {
+    let mut dial = Dial::with_params(params);
+
+    input
+        .iter()
+        .filter(|&&(dir, clicks)| dial.rotate(dir, clicks).landed)
+        .count()
 }
 
 #[aoc(day1, part2)]
-pub fn solve_part2(input: &Vec<(Dir, isize, usize)>) -> usize {
-    let mut dial = 50;
-    let mut count = 0;
+pub fn solve_part2(input: &[(Dir, u64)]) -> u64 {
+    solve_part2_with(input, &Params::default())
+}
 
-    for (dir, steps, spins) in input {
-        let mut add_count = false;
-        let dial_start = dial;
+pub fn solve_part2_with(input: &[(Dir, u64)], params: &Params) -> u64 {
+    let mut dial = Dial::with_params(params);
 
-        match dir {
-            Dir::Left => dial -= steps,
-            Dir::Right => dial += steps,
-        }
+    input
+        .iter()
+        .map(|&(dir, clicks)| dial.rotate(dir, clicks).zeros)
+        .sum()
+}
 
-        if dial > 99 {
-            dial -= 100;
-            add_count = true;
-        } else if dial < 0 {
-            dial += 100;
-            if dial_start != 0 {
-                add_count = true;
-            }
-        }
+pub struct Day01;
 
-        if spins > &0 {
-            count += spins;
-        }
+impl Solution for Day01 {
+    const DAY: u32 = 1;
 
-        if dial == 0 || add_count {
-            count += 1;
-        }
+    type Input = Vec<(Dir, u64)>;
+    type Params = Params;
+
+    fn parse(input: &str, _params: &Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input, ctx: &Context<Params>) -> Answer {
+        solve_part1_with(input, ctx.params()).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, ctx| solve_part2_with(input, ctx.params()).into())
     }
 
-    count
+    fn trace(input: &Self::Input, ctx: &Context<Params>) -> Option<String> {
+        Some(trace_table(input, ctx.params()))
+    }
 }
 
 #[cfg(test)]
@@ -101,11 +266,106 @@ L82";
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 3);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 3);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 6);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), 6);
+    }
+
+    #[test]
+    fn full_turn_from_zero_test() {
+        assert_eq!(solve_part2(&input_generator("L50\nR100").unwrap()), 2);
+    }
+
+    #[test]
+    fn params_test() {
+        let params = Params { positions: 10, start: 0 };
+        let input = input_generator("R5\nR5\nL20\nR3").unwrap();
+        assert_eq!(solve_part1_with(&input, &params), 2);
+        assert_eq!(solve_part2_with(&input, &params), 3);
+
+        let err = crate::params::from_settings::<Params>(&[("positions".to_string(), "0".to_string())]).unwrap_err();
+        assert_eq!((err.expected.as_str(), err.found.as_str()), ("at least one position", "positions=0"));
+    }
+
+    /// Position, zeros and first zero after turning one click at a time.
+    fn click_by_click(size: u64, start: u64, dir: Dir, clicks: u64) -> (u64, u64, Option<u64>) {
+        let (mut position, mut zeros, mut first_zero) = (start, 0, None);
+        for click in 1..=clicks {
+            position = match dir {
+                Dir::Left  => (position + size - 1) % size,
+                Dir::Right => (position + 1) % size,
+            };
+            if position == 0 {
+                zeros += 1;
+                first_zero = first_zero.or(Some(click));
+            }
+        }
+
+        (position, zeros, first_zero)
+    }
+
+    #[test]
+    fn dial_test() {
+        for size in 1..=12 {
+            for start in 0..size {
+                for dir in [Dir::Left, Dir::Right] {
+                    for clicks in 0..=size * 3 + 1 {
+                        let mut dial = Dial::new(size, start);
+                        let turn = dial.rotate(dir, clicks);
+                        let case = format!("size {size}, start {start}, {dir:?} {clicks}");
+                        assert_eq!((dial.position(), turn.zeros, turn.first_zero), click_by_click(size, start, dir, clicks), "{case}");
+                        assert_eq!(turn.landed, dial.position() == 0, "{case}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_rotation_test() {
+        let mut dial = Dial::new(100, 50);
+        assert_eq!(dial.rotate(Dir::Right, 1_000_000_000_000_000_000).zeros, 10_000_000_000_000_000);
+        assert_eq!(dial.position(), 50);
+        assert_eq!(dial.rotate(Dir::Left, u64::MAX).zeros, (u64::MAX - 50) / 100 + 1);
+        assert_eq!(dial.position(), (50 + 100 - u64::MAX % 100) % 100);
+        let mut dial = Dial::new(u64::MAX, u64::MAX - 1);
+        assert_eq!(dial.rotate(Dir::Right, 3), Turn { landed: false, zeros: 1, first_zero: Some(1) });
+        assert_eq!(dial.position(), 2);
+        assert_eq!(dial.rotate(Dir::Left, 4), Turn { landed: false, zeros: 1, first_zero: Some(2) });
+        assert_eq!(dial.position(), u64::MAX - 2);
+    }
+
+    #[test]
+    fn trace_test() {
+        let input = input_generator(TEST).unwrap();
+        let steps: Vec<Step> = trace(&input, &Params::default()).collect();
+        assert_eq!(steps.len(), 10);
+        assert_eq!((steps[0].from, steps[0].to, steps[0].turn.first_zero), (50, 82, Some(50)));
+        assert_eq!(steps.iter().map(|step| step.turn.zeros).sum::<u64>(), 6);
+
+        let histogram = histogram(steps.iter().copied());
+        assert_eq!(histogram[&0], (3, 2));
+        assert_eq!(histogram.values().map(|&(count, _)| count).sum::<usize>(), 10);
+        assert_eq!(first_landing(steps.iter().copied(), 0), Some(2));
+        assert_eq!(first_landing(steps.iter().copied(), 1), None);
+
+        let input = input_generator("R240").unwrap();
+        let step = trace(&input, &Params::default()).next().unwrap();
+        assert_eq!(step.zero_clicks().collect::<Vec<_>>(), [50, 150]);
+    }
+
+    #[test]
+    fn crlf_test() {
+        assert_eq!(solve_part2(&input_generator(&TEST.replace('\n', "\r\n")).unwrap()), 6);
+    }
+
+    #[test]
+    fn error_test() {
+        let err = input_generator("L68\nL30\nX48").unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (1, 3, 1));
+        assert_eq!(err.found, "X");
     }
 }
\ No newline at end of file
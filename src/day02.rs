@@ -1,88 +1,380 @@
+use crate::prelude::*;
 use rayon::prelude::*;
 
 #[aoc_generator(day2)]
-pub fn input_generator(input: &str) -> Vec<(usize, usize)> {
-    input
-        .split(',')
-        .map(|range| range.split('-'))
-        .filter_map(|mut ids| {
-            Some((
-                ids.next()?.parse().ok()?,
-                ids.next()?.parse().ok()?
-            ))
+pub fn input_generator(input: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+    let mut ranges = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        let mut offset = 0;
+
+        for range in line.split(',') {
+            if !range.is_empty() {
+                ranges.push(
+                    parse_line!(range, "{}-{}" => usize, usize)
+                        .map_err(|err| err.within(line, offset).at_line(idx + 1).on_day(2))?
+                );
+            }
+            offset += range.len() + 1;
+        }
+    }
+
+    Ok(ranges)
+}
+
+/// Sums `check`ed IDs over every range, reporting each range's IDs to the
+/// context's progress as it is done. A range that ends before it starts has
+/// no IDs.
+fn sum_ids(input: &[(usize, usize)], ctx: &Context, check: impl Fn(usize) -> bool + Sync) -> usize {
+    let progress = ctx.progress();
+    progress.start("IDs", input.iter().map(|&(start, end)| end.checked_sub(start).map_or(0, |span| span as u64 + 1)).sum());
+
+    let sum = input
+        .par_iter()
+        .map(|&(start, end)| {
+            let sum: usize = (start..=end).into_par_iter().filter(|&n| check(n)).sum();
+            progress.advance(end.checked_sub(start).map_or(0, |span| span as u64 + 1));
+            sum
         })
-        .collect()
+        .sum();
+
+    progress.finish();
+    sum
 }
 
 #[aoc(day2, part1)]
-pub fn solve_part1(input: &Vec<(usize, usize)>) -> usize {
-    input
-        .par_iter()
-        .flat_map(|&(start, end)| {
-            (start..=end).into_par_iter()
-                .filter(|&n| {
-                    let mag = n.ilog10() as usize + 1;
-                    mag % 2 == 0
-                })
-                .filter(|&n| {
-                    let num_str = n.to_string();
-                    let (left, right) = num_str.split_at(num_str.len() / 2);
-                    left == right
-                })
-                .map(|n| n)
+pub fn solve_part1(input: &[(usize, usize)]) -> usize {
+    solve_part1_with(input, &Context::default())
+}
+
+pub fn solve_part1_with(input: &[(usize, usize)], ctx: &Context) -> usize {
+    sum_ids(input, ctx, |n| is_repeated(n as u128, 10, Repeats::Exactly(2)))
+}
+
+#[aoc(day2, part2)]
+pub fn solve_part2(input: &[(usize, usize)]) -> usize {
+    solve_part2_with(input, &Context::default())
+}
+
+pub fn solve_part2_with(input: &[(usize, usize)], ctx: &Context) -> usize {
+    sum_ids(input, ctx, |n| is_repeated(n as u128, 10, Repeats::AtLeastTwice))
+}
+
+/// Which repeated IDs to pick out. An ID repeats if its digits are a
+/// shorter block of digits over and over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeats {
+    /// A block repeated exactly this many times. 222222 is 2 repeated six
+    /// times but also 222 repeated twice, so it counts for 2, 3 and 6.
+    Exactly(u32),
+    /// Any block repeated any number of times from two up.
+    AtLeastTwice,
+    /// IDs whose shortest repeating block has this many digits. An ID that
+    /// doesn't repeat at all is its own block, so `Period(3)` includes 123
+    /// as well as 123123.
+    Period(u32),
+}
+
+fn digits(id: u128, radix: u32) -> u32 {
+    if id == 0 { 1 } else { id.ilog(radix as u128) + 1 }
+}
+
+/// What an ID with `length` digits is its block of `block` digits times: 1
+/// followed by `block - 1` zeros and a 1, over and over. `None` if that
+/// doesn't fit, in which case no such ID does either.
+fn repeat_factor(radix: u32, length: u32, block: u32) -> Option<u128> {
+    // A single block is the ID itself, however large a shift past it would be.
+    if block == length {
+        return Some(1);
+    }
+
+    let shift = (radix as u128).checked_pow(block)?;
+    (1..length / block).try_fold(1_u128, |factor, _| factor.checked_mul(shift)?.checked_add(1))
+}
+
+/// The length of an ID's shortest repeating block.
+fn period(id: u128, radix: u32) -> u32 {
+    let length = digits(id, radix);
+    (1..length)
+        .filter(|&block| length.is_multiple_of(block))
+        .find(|&block| {
+            repeat_factor(radix, length, block)
+                .is_some_and(|factor| id.is_multiple_of(factor) && id / factor < (radix as u128).pow(block))
         })
-        .sum()
+        .unwrap_or(length)
 }
 
-fn is_invalid(n: usize) -> bool {
-    let num_str = n.to_string();
-    let mag = num_str.len();
+/// Whether `id`, written in `radix`, is one of the `repeats`.
+pub fn is_repeated(id: u128, radix: u32, repeats: Repeats) -> bool {
+    assert!((2..=36).contains(&radix), "radix must be from 2 to 36");
+    let (length, period) = (digits(id, radix), period(id, radix));
 
-    for chunk_size in 1..=mag / 2 {
-        if mag % chunk_size == 0 {
-            let mut valid = true;
-            for i in 0..mag-chunk_size {
-                if num_str.chars().nth(i) != num_str.chars().nth(i + chunk_size) {
-                    valid = false;
-                    break;
-                }
-            }
-            if valid {
-                return true;
+    match repeats {
+        Repeats::Exactly(times) => times > 0 && length.is_multiple_of(times) && (length / times).is_multiple_of(period),
+        Repeats::AtLeastTwice   => period < length,
+        Repeats::Period(block)  => period == block,
+    }
+}
+
+/// Sum of the IDs in `start..=end` with `length` digits that are a block of
+/// `block` digits repeated, whether or not the block repeats itself. The
+/// blocks that land in range are consecutive, so this is a triangle number
+/// times the repeat factor.
+fn block_sum(start: u128, end: u128, radix: u32, length: u32, block: u32) -> Option<u128> {
+    let Some(factor) = repeat_factor(radix, length, block) else {
+        return Some(0);
+    };
+    let largest_block = (radix as u128).checked_pow(block).map_or(u128::MAX, |power| power - 1);
+    let first = max((radix as u128).pow(block - 1), start.div_ceil(factor));
+    let last = min(largest_block, end / factor);
+    if first > last {
+        return Some(0);
+    }
+
+    // first * count plus 0 + 1 + ... + (count - 1), halving whichever of count
+    // and count - 1 is even before multiplying, so nothing overflows early.
+    let count = last - first + 1;
+    let steps = if count.is_multiple_of(2) { (count / 2).checked_mul(count - 1)? } else { count.checked_mul((count - 1) / 2)? };
+    first.checked_mul(count)?.checked_add(steps)?.checked_mul(factor)
+}
+
+/// Sum of the IDs in `start..=end` with `length` digits whose shortest
+/// repeating block has `block` digits: those made of `block` digit blocks,
+/// less those made of any shorter block that divides it.
+fn period_sum(start: u128, end: u128, radix: u32, length: u32, block: u32) -> Option<u128> {
+    (1..block)
+        .filter(|&shorter| block.is_multiple_of(shorter))
+        .try_fold(block_sum(start, end, radix, length, block)?, |sum, shorter| {
+            sum.checked_sub(period_sum(start, end, radix, length, shorter)?)
+        })
+}
+
+/// Sum of the `repeats` in `start..=end`, written in `radix`, in time that
+/// depends on how many digits the IDs have rather than how many there are.
+/// `None` if the sum doesn't fit in a `u128`.
+pub fn sum_repeated(start: u128, end: u128, radix: u32, repeats: Repeats) -> Option<u128> {
+    assert!((2..=36).contains(&radix), "radix must be from 2 to 36");
+    if start > end {
+        return Some(0);
+    }
+
+    // IDs of each length are summed on their own.
+    (digits(start, radix)..=digits(end, radix)).try_fold(0_u128, |sum, length| {
+        let length_sum = match repeats {
+            Repeats::Exactly(0)     => Some(0),
+            Repeats::Exactly(times) if length.is_multiple_of(times) => block_sum(start, end, radix, length, length / times),
+            Repeats::Exactly(_)     => Some(0),
+            Repeats::AtLeastTwice   => (1..length)
+                .filter(|&block| length.is_multiple_of(block))
+                .try_fold(0_u128, |sum, block| sum.checked_add(period_sum(start, end, radix, length, block)?)),
+            Repeats::Period(0)      => Some(0),
+            Repeats::Period(block) if length.is_multiple_of(block) => period_sum(start, end, radix, length, block),
+            Repeats::Period(_)      => Some(0),
+        };
+        sum.checked_add(length_sum?)
+    })
+}
+
+/// Parses ranges of IDs written in `radix`, for IDs beyond the puzzle's.
+pub fn ranges_in_radix(input: &str, radix: u32) -> Result<Vec<(u128, u128)>, ParseError> {
+    assert!((2..=36).contains(&radix), "radix must be from 2 to 36");
+    let mut ranges = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        let mut offset = 0;
+
+        for range in line.split(',') {
+            if !range.is_empty() {
+                let (start, end) = range
+                    .split_once('-')
+                    .ok_or_else(|| ParseError::new(range, range.len() + 1, "`-`", ""))
+                    .and_then(|(start, end)| {
+                        let id = |id: &str, column| {
+                            u128::from_str_radix(id, radix)
+                                .map_err(|_| ParseError::new(range, column, format!("a base {radix} ID"), id))
+                        };
+                        Ok((id(start, 1)?, id(end, start.len() + 2)?))
+                    })
+                    .map_err(|err| err.within(line, offset).at_line(idx + 1).on_day(2))?;
+                ranges.push((start, end));
             }
+            offset += range.len() + 1;
         }
     }
 
-    false
+    Ok(ranges)
 }
 
-#[aoc(day2, part2)]
-pub fn solve_part2(input: &Vec<(usize, usize)>) -> usize {
+/// The puzzle's ranges, summed as `u128`s in base 10.
+fn sum_decimal(input: &[(usize, usize)], repeats: Repeats) -> usize {
     input
-        .par_iter()
-        .flat_map(|&(start, end)| {
-            (start..=end).into_par_iter()
-                .filter(|&n| {
-                    is_invalid(n)
-                })
-                .map(|n| n)
-        })
-        .sum()
+        .iter()
+        .map(|&(start, end)| sum_repeated(start as u128, end as u128, 10, repeats).expect("sum fits in a u128"))
+        .sum::<u128>() as usize
+}
+
+#[aoc(day2, part1, Arithmetic)]
+pub fn solve_part1_arithmetic(input: &[(usize, usize)]) -> usize {
+    sum_decimal(input, Repeats::Exactly(2))
+}
+
+#[aoc(day2, part2, Arithmetic)]
+pub fn solve_part2_arithmetic(input: &[(usize, usize)]) -> usize {
+    sum_decimal(input, Repeats::AtLeastTwice)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<(usize, usize)>;
+    type Params = ();
+
+    fn parse(input: &str, _params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input, ctx: &Context) -> Answer {
+        solve_part1_with(input, ctx).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, ctx| solve_part2_with(input, ctx).into())
+    }
+
+    fn alternatives() -> Vec<(u32, &'static str, DaySolver<Self>)> {
+        vec![
+            (1, "Arithmetic", |input, _ctx| solve_part1_arithmetic(input).into()),
+            (2, "Arithmetic", |input, _ctx| solve_part2_arithmetic(input).into()),
+        ]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate::{Generator, IdRanges};
 
     const TEST: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 1227775554);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 1227775554);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 4174379265);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), 4174379265);
+    }
+
+    #[test]
+    fn reversed_range_test() {
+        let input = input_generator("22-11,11-22").unwrap();
+        assert_eq!(solve_part1(&input), 33);
+        assert_eq!(solve_part1_arithmetic(&input), 33);
+        assert_eq!(solve_part1(&[(usize::MAX, usize::MAX)]), 0);
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let input = input_generator(TEST).unwrap();
+        assert_eq!(solve_part1_arithmetic(&input), 1227775554);
+        assert_eq!(solve_part2_arithmetic(&input), 4174379265);
+
+        for seed in 0..50 {
+            let input = input_generator(&IdRanges { ranges: 10, max_digits: 8, max_span: 5000 }.generate_seeded(seed)).unwrap();
+            assert_eq!(solve_part1_arithmetic(&input), solve_part1(&input), "seed {seed}");
+            assert_eq!(solve_part2_arithmetic(&input), solve_part2(&input), "seed {seed}");
+        }
+
+        // Every ID with up to twelve digits, far too many to walk.
+        let halves = (1..=6).map(|half| {
+            let (low, high) = (10_usize.pow(half - 1), 10_usize.pow(half) - 1);
+            (high + 2) * (low + high) * (high - low + 1) / 2
+        });
+        assert_eq!(solve_part1_arithmetic(&[(1, 999_999_999_999)]), halves.sum());
+    }
+
+    /// Digits of `id` in `radix`, most significant first.
+    fn digits_of(mut id: u128, radix: u32) -> Vec<u128> {
+        let mut digits = vec![id % radix as u128];
+        while id >= radix as u128 {
+            id /= radix as u128;
+            digits.push(id % radix as u128);
+        }
+        digits.reverse();
+        digits
+    }
+
+    fn repeats_by_digits(id: u128, radix: u32, repeats: Repeats) -> bool {
+        let digits = digits_of(id, radix);
+        let made_of = |block: usize| digits.len().is_multiple_of(block) && digits.chunks(block).all(|chunk| chunk == &digits[..block]);
+        let shortest = (1..=digits.len()).find(|&block| made_of(block)).unwrap();
+
+        match repeats {
+            Repeats::Exactly(times) => times > 0 && digits.len().is_multiple_of(times as usize) && made_of(digits.len() / times as usize),
+            Repeats::AtLeastTwice   => shortest < digits.len(),
+            Repeats::Period(block)  => shortest == block as usize,
+        }
+    }
+
+    #[test]
+    fn radix_test() {
+        let queries = [Repeats::Exactly(2), Repeats::Exactly(3), Repeats::AtLeastTwice, Repeats::Period(1), Repeats::Period(2)];
+        for radix in [2, 3, 7, 10, 16, 36] {
+            for start in (0..3000).step_by(97) {
+                let end = start + 500;
+                for repeats in queries {
+                    let expected: u128 = (start..=end).filter(|&id| repeats_by_digits(id, radix, repeats)).sum();
+                    assert_eq!(sum_repeated(start, end, radix, repeats), Some(expected), "{start}-{end} base {radix} {repeats:?}");
+                }
+                for id in start..start + 50 {
+                    for repeats in queries {
+                        assert_eq!(is_repeated(id, radix, repeats), repeats_by_digits(id, radix, repeats), "{id} base {radix} {repeats:?}");
+                    }
+                }
+            }
+
+            // Every ID has exactly one shortest block.
+            let periods: u128 = (1..=12).map(|block| sum_repeated(1, 4000, radix, Repeats::Period(block)).unwrap()).sum();
+            assert_eq!(periods, (1..=4000).sum());
+        }
+    }
+
+    #[test]
+    fn u128_test() {
+        // 32 hex digits of f, which is f repeated, ff repeated and so on.
+        let top = (u128::MAX - 1000, u128::MAX);
+        assert!(is_repeated(u128::MAX, 16, Repeats::Exactly(32)));
+        assert_eq!(sum_repeated(top.0, top.1, 16, Repeats::Exactly(2)), Some(u128::MAX));
+        assert_eq!(sum_repeated(top.0, top.1, 16, Repeats::Period(1)), Some(u128::MAX));
+        assert_eq!(sum_repeated(top.0, top.1, 2, Repeats::AtLeastTwice), Some(u128::MAX));
+        assert_eq!(sum_repeated(0, u128::MAX, 10, Repeats::AtLeastTwice), None);
+        let ones: u128 = "1".repeat(39).parse().unwrap();
+        assert_eq!(sum_repeated(0, 2 * ones, 10, Repeats::Exactly(39)), Some(3 * ones));
+        assert_eq!(sum_repeated(0, 3 * ones, 10, Repeats::Exactly(39)), None);
+
+        // 39 digits, so a single block of them is the whole ID.
+        let low = 10_u128.pow(38);
+        assert!(is_repeated(low, 10, Repeats::Period(39)));
+        assert_eq!(sum_repeated(low, low, 10, Repeats::Period(39)), Some(low));
+        assert_eq!(sum_repeated(low, low + 2, 10, Repeats::Exactly(1)), Some(3 * low + 3));
+        assert_eq!(sum_repeated(low, low + 2, 10, Repeats::Period(39)), Some(3 * low + 3));
+        assert_eq!(sum_repeated(1 << 127, 1 << 127, 2, Repeats::Period(128)), Some(1 << 127));
+        assert_eq!(sum_repeated(low, 3 * low, 10, Repeats::Exactly(1)), None);
+        assert_eq!(is_repeated(u128::MAX - 1, 3, Repeats::AtLeastTwice), repeats_by_digits(u128::MAX - 1, 3, Repeats::AtLeastTwice));
+    }
+
+    #[test]
+    fn ranges_in_radix_test() {
+        assert_eq!(ranges_in_radix("ff-1ff,z-zz\n10-11", 36).unwrap(), [(555, 1851), (35, 1295), (36, 37)]);
+        assert_eq!(ranges_in_radix("ff-1ff", 16).unwrap(), [(255, 511)]);
+
+        let err = ranges_in_radix("1-2,ff-1g", 16).unwrap_err();
+        assert_eq!((err.day, err.line, err.column, err.found.as_str()), (2, 1, 8, "1g"));
+        assert_eq!(ranges_in_radix("1-2,3", 10).unwrap_err().column, 6);
     }
 }
\ No newline at end of file
@@ -1,91 +1,334 @@
+use crate::prelude::*;
 use rayon::prelude::*;
 
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Batteries turned on in each bank for part 2.
+    pub batteries: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { batteries: 12 }
+    }
+}
+
+params!(Params { batteries });
+
 #[aoc_generator(day3)]
-pub fn input_generator(input: &str) -> Vec<Vec<usize>> {
+pub fn input_generator(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    input_generator_with(input, &Params::default())
+}
+
+// Every bank needs enough batteries for both parts.
+pub fn input_generator_with(input: &str, params: &Params) -> Result<Vec<Vec<usize>>, ParseError> {
+    let needed = max(2, params.batteries);
+
     input
         .lines()
-        .map(|line| {
-            line.chars()
-                .map(|char| char.to_digit(10).unwrap() as usize)
-                .collect()
+        .enumerate()
+        .map(|(row, line)| {
+            let line = line.trim_end();
+            let bank: Vec<usize> = line.chars()
+                .enumerate()
+                .map(|(col, char)| match char.to_digit(10) {
+                    Some(digit) => Ok(digit as usize),
+                    None => Err(ParseError::new(line, col + 1, "a digit", char).at_line(row + 1).on_day(3)),
+                })
+                .collect::<Result<_, _>>()?;
+
+            if bank.len() < needed {
+                let expected = format!("at least {needed} batteries");
+                return Err(ParseError::new(line, line.len() + 1, expected, "end of line").at_line(row + 1).on_day(3));
+            }
+
+            Ok(bank)
         })
         .collect()
 }
 
-#[aoc(day3, part1)]
-pub fn solve_part1(input: &Vec<Vec<usize>>) -> usize {
-    let mut sum = 0;
-
-    for line in input {
-        let (mut first, mut second) = (line[0], line[1]);
-
-        for num in &line[2..] {
-            if second > first {
-                first = second;
-                second = *num;
-            } else if num > &second {
-                second = *num;
-            }
-        }
+/// Batteries picked from a bank, in bank order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    /// Where each battery is in the bank, increasing.
+    pub indices: Vec<usize>,
+    /// Each battery's joltage digit.
+    pub digits: Vec<usize>,
+}
 
-        sum += first * 10 + second;
+impl Selection {
+    /// The joltage the batteries make together, or `None` if it has more
+    /// digits than a `u128` holds. `Display` shows it however long it is.
+    pub fn value(&self) -> Option<u128> {
+        self.digits
+            .iter()
+            .try_fold(0_u128, |value, &digit| value.checked_mul(10)?.checked_add(digit as u128))
     }
+}
+
+impl fmt::Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for digit in &self.digits {
+            write!(f, "{digit}")?;
+        }
 
-    sum
+        Ok(())
+    }
 }
 
-fn find_first_smallest(digits: &[usize]) -> Option<usize> {
-    for (index, window) in digits.windows(2).enumerate() {
-        if window[0] == 1 {
-            return Some(index);
+/// Picks the `count` batteries that make the largest joltage, keeping them in
+/// order. A battery beats any smaller one before it, as long as there are
+/// enough batteries left to make up the count without the smaller one. `None`
+/// if the bank has fewer than `count` batteries.
+pub fn max_selection(bank: &[usize], count: usize) -> Option<Selection> {
+    if count > bank.len() {
+        return None;
+    }
+
+    let mut stack: Vec<usize> = Vec::with_capacity(count);
+
+    for (idx, &digit) in bank.iter().enumerate() {
+        while let Some(&top) = stack.last()
+            && bank[top] < digit
+            && stack.len() + (bank.len() - idx) > count
+        {
+            stack.pop();
         }
-        
-        if window[0] < window[1] {
-            return Some(index);
+        if stack.len() < count {
+            stack.push(idx);
         }
     }
 
-    None
+    Some(Selection { digits: stack.iter().map(|&idx| bank[idx]).collect(), indices: stack })
 }
 
-fn shift_left(digits: &mut [usize], index: usize) {
-    for idx in index..digits.len() - 1 {
-        digits[idx] = digits[idx + 1];
+/// Adds up joltages digit by digit, so it works for any number of batteries.
+fn total(selections: &[Selection]) -> Answer {
+    // Least significant digit first.
+    let mut sum: Vec<usize> = Vec::new();
+    for selection in selections {
+        let mut digits = selection.digits.iter().rev();
+        let (mut idx, mut carry) = (0, 0);
+        while let Some(digit) = digits.next().or((carry > 0).then_some(&0)) {
+            if idx == sum.len() {
+                sum.push(0);
+            }
+            let column = sum[idx] + digit + carry;
+            (sum[idx], carry) = (column % 10, column / 10);
+            idx += 1;
+        }
     }
+
+    let text: String = sum.iter().rev().map(|digit| digit.to_string()).collect();
+    if text.is_empty() { Answer::Int(0) } else { text.parse().expect("parsing an answer can't fail") }
 }
 
-fn digits_to_number(digits: &[usize]) -> usize {
-    digits.iter().rev().enumerate().fold(0, |acc, (i, &digit)| {
-        acc + digit * 10usize.pow(i as u32)
-    })
+/// Which end of the order selections come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Largest,
+    Smallest,
 }
 
-#[aoc(day3, part2)]
-pub fn solve_part2(input: &Vec<Vec<usize>>) -> usize {
-    input.par_iter()
-        .map(|line| {
-            let mut digits: [usize; 12] = [0; 12];
-            for (idx, &num) in line.iter().take(12).enumerate() {
-                digits[idx] = num;
-            }
+/// What a selection has to satisfy besides keeping bank order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// Batteries to turn on.
+    pub count: usize,
+    /// Least distance between the positions of two chosen batteries, 1 to
+    /// allow neighbours.
+    pub gap: usize,
+    /// What the joltage has to be a multiple of. The search keeps a table
+    /// entry per remainder, so this should stay modest.
+    pub divisor: u64,
+}
 
-            for num in &line[12..] {
-                if let Some(index) = find_first_smallest(&digits) {
-                    shift_left(&mut digits, index);
-                    digits[11] = *num;
-                } else if num > &digits[11] {
-                    digits[11] = *num;
+impl Rules {
+    pub fn new(count: usize) -> Self {
+        Self { count, gap: 1, divisor: 1 }
+    }
+
+    pub fn with_gap(self, gap: usize) -> Self {
+        assert!(gap > 0, "the gap is between distinct positions");
+        Self { gap, ..self }
+    }
+
+    pub fn divisible_by(self, divisor: u64) -> Self {
+        assert!(divisor > 0, "nothing is a multiple of zero");
+        Self { divisor, ..self }
+    }
+}
+
+fn mul_mod(a: u64, b: u64, divisor: u64) -> u64 {
+    (a as u128 * b as u128 % divisor as u128) as u64
+}
+
+/// Finds selections under `Rules` digit by digit, from the most significant.
+/// Each digit goes at the earliest position holding it that still leaves a
+/// way to finish, since an earlier position leaves at least as many ways.
+/// Whether there is a way to finish is looked up in a table of which
+/// remainders `r` more batteries can make from each position on.
+pub struct Selector<'a> {
+    bank: &'a [usize],
+    rules: Rules,
+    /// The first position at or after each one holding each digit.
+    next: Vec<[Option<usize>; 10]>,
+    /// `remainders[r][i][rem]`: some `r` batteries from position `i` on make
+    /// a joltage with remainder `rem`.
+    remainders: Vec<Vec<Vec<bool>>>,
+    /// Powers of ten, modulo the divisor.
+    powers: Vec<u64>,
+}
+
+impl<'a> Selector<'a> {
+    pub fn new(bank: &'a [usize], rules: Rules) -> Self {
+        let (len, divisor) = (bank.len(), rules.divisor);
+
+        let mut next = vec![[None; 10]; len + 1];
+        for idx in (0..len).rev() {
+            next[idx] = next[idx + 1];
+            next[idx][bank[idx]] = Some(idx);
+        }
+
+        let powers: Vec<u64> = (0..=rules.count).scan(1 % divisor, |power, _| {
+            let current = *power;
+            *power = mul_mod(current, 10, divisor);
+            Some(current)
+        }).collect();
+
+        let mut remainders = vec![vec![vec![false; divisor as usize]; len + 1]; rules.count + 1];
+        for reachable in &mut remainders[0] {
+            reachable[0] = true;
+        }
+        for left in 1..=rules.count {
+            for idx in (0..len).rev() {
+                let after = min(idx + rules.gap, len);
+                let mut reachable = remainders[left][idx + 1].clone();
+                for (rest, _) in remainders[left - 1][after].iter().enumerate().filter(|&(_, &ok)| ok) {
+                    reachable[((mul_mod(bank[idx] as u64, powers[left - 1], divisor) + rest as u64) % divisor) as usize] = true;
                 }
+                remainders[left][idx] = reachable;
             }
+        }
 
-            digits_to_number(&digits)
-        })
+        Self { bank, rules, next, remainders, powers }
+    }
+
+    /// The best selection in `order`, if any satisfies the rules.
+    pub fn best(&self, order: Order) -> Option<Selection> {
+        self.top(order, 1).pop()
+    }
+
+    /// Up to `limit` selections with distinct joltages, best first.
+    pub fn top(&self, order: Order, limit: usize) -> Vec<Selection> {
+        let mut found = Vec::new();
+        if self.remainders[self.rules.count][0][0] && limit > 0 {
+            self.search(order, limit, 0, 0, &mut Vec::new(), &mut found);
+        }
+
+        found
+    }
+
+    /// Tries each digit in `order` for the next battery, looking no earlier
+    /// than `start`, with `chosen` so far making remainder `prefix`.
+    fn search(&self, order: Order, limit: usize, start: usize, prefix: u64, chosen: &mut Vec<usize>, found: &mut Vec<Selection>) {
+        let left = self.rules.count - chosen.len();
+        if left == 0 {
+            found.push(Selection { digits: chosen.iter().map(|&idx| self.bank[idx]).collect(), indices: chosen.clone() });
+            return;
+        }
+
+        let divisor = self.rules.divisor;
+        let digits: Vec<usize> = match order {
+            Order::Largest  => (0..10).rev().collect(),
+            Order::Smallest => (0..10).collect(),
+        };
+
+        for digit in digits {
+            let Some(idx) = self.next[min(start, self.bank.len())][digit] else {
+                continue;
+            };
+            // What the rest has to make up for the whole to be a multiple.
+            let prefix = (mul_mod(prefix, 10, divisor) + digit as u64) % divisor;
+            let needed = (divisor - mul_mod(prefix, self.powers[left - 1], divisor)) % divisor;
+            if !self.remainders[left - 1][min(idx + self.rules.gap, self.bank.len())][needed as usize] {
+                continue;
+            }
+
+            chosen.push(idx);
+            self.search(order, limit, idx + self.rules.gap, prefix, chosen, found);
+            chosen.pop();
+            if found.len() == limit {
+                return;
+            }
+        }
+    }
+}
+
+/// The `count` batteries that make the smallest joltage.
+pub fn min_selection(bank: &[usize], count: usize) -> Option<Selection> {
+    Selector::new(bank, Rules::new(count)).best(Order::Smallest)
+}
+
+/// The largest joltage with at least `gap` between chosen positions.
+pub fn max_with_gap(bank: &[usize], count: usize, gap: usize) -> Option<Selection> {
+    Selector::new(bank, Rules::new(count).with_gap(gap)).best(Order::Largest)
+}
+
+/// The largest joltage that is a multiple of `divisor`.
+pub fn max_divisible(bank: &[usize], count: usize, divisor: u64) -> Option<Selection> {
+    Selector::new(bank, Rules::new(count).divisible_by(divisor)).best(Order::Largest)
+}
+
+/// The `limit` largest distinct joltages, largest first.
+pub fn top_selections(bank: &[usize], count: usize, limit: usize) -> Vec<Selection> {
+    Selector::new(bank, Rules::new(count)).top(Order::Largest, limit)
+}
+
+#[aoc(day3, part1)]
+pub fn solve_part1(input: &[Vec<usize>]) -> usize {
+    input
+        .iter()
+        .map(|bank| max_selection(bank, 2).expect("banks have at least two batteries").digits.iter().fold(0, |value, digit| value * 10 + digit))
         .sum()
 }
 
+#[aoc(day3, part2)]
+pub fn solve_part2(input: &[Vec<usize>]) -> Answer {
+    solve_part2_with(input, &Params::default())
+}
+
+pub fn solve_part2_with(input: &[Vec<usize>], params: &Params) -> Answer {
+    let selections: Option<Vec<Selection>> = input.par_iter().map(|bank| max_selection(bank, params.batteries)).collect();
+
+    total(&selections.expect("input_generator_with checks every bank is long enough"))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+
+    type Input = Vec<Vec<usize>>;
+    type Params = Params;
+
+    fn parse(input: &str, params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator_with(input, params)
+    }
+
+    fn part1(input: &Self::Input, _ctx: &Context<Params>) -> Answer {
+        solve_part1(input).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, ctx| solve_part2_with(input, ctx.params()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate::{BatteryBanks, Generator};
 
     const TEST: &str = "987654321111111
 811111111111119
@@ -94,11 +337,104 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 357);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 357);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 3121910778619);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), Answer::Int(3121910778619));
+    }
+
+    #[test]
+    fn batteries_test() {
+        let input = input_generator(TEST).unwrap();
+        assert_eq!(solve_part2_with(&input, &Params { batteries: 2 }), solve_part1(&input).into());
+        assert_eq!(solve_part2_with(&input, &Params { batteries: 3 }), (987 + 819 + 478 + 921).into());
+    }
+
+    #[test]
+    fn selection_test() {
+        let bank = input_generator("818181911112111").unwrap().remove(0);
+        let selection = max_selection(&bank, 12).unwrap();
+        assert_eq!(selection.to_string(), "888911112111");
+        assert_eq!(selection.indices, [0, 2, 4, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+        assert_eq!(selection.value(), Some(888911112111));
+        assert_eq!(max_selection(&bank, 0).unwrap().value(), Some(0));
+        assert_eq!(max_selection(&bank, bank.len() + 1), None);
+        assert_eq!(max_selection(&bank, bank.len()).unwrap().indices, (0..bank.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn short_bank_test() {
+        let err = input_generator_with("9876\n98", &Params { batteries: 3 }).unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 3, "at least 3 batteries"));
+        assert_eq!(input_generator_with("98", &Params { batteries: 1 }).unwrap(), [[9, 8]]);
+        assert!(input_generator_with("9", &Params { batteries: 1 }).is_err());
+    }
+
+    /// Every selection the rules allow, by trying every set of positions.
+    fn every_selection(bank: &[usize], rules: Rules) -> Vec<Selection> {
+        (0_u32..1 << bank.len())
+            .filter(|mask| mask.count_ones() as usize == rules.count)
+            .map(|mask| {
+                let indices: Vec<usize> = (0..bank.len()).filter(|idx| mask & 1 << idx != 0).collect();
+                Selection { digits: indices.iter().map(|&idx| bank[idx]).collect(), indices }
+            })
+            .filter(|selection| selection.indices.windows(2).all(|pair| pair[1] - pair[0] >= rules.gap))
+            .filter(|selection| selection.value().unwrap() % rules.divisor as u128 == 0)
+            .collect()
+    }
+
+    #[test]
+    fn selector_test() {
+        let input = input_generator(&BatteryBanks { banks: 40, batteries: 12 }.generate_seeded(3)).unwrap();
+
+        for (case, bank) in input.iter().enumerate() {
+            let count = 1 + case % 6;
+            let rules = [
+                Rules::new(count),
+                Rules::new(count).with_gap(1 + case % 3),
+                Rules::new(count).divisible_by(1 + case as u64 % 13),
+                Rules::new(count).with_gap(2).divisible_by(7),
+            ];
+            for rules in rules {
+                let mut values: Vec<u128> = every_selection(bank, rules).iter().map(|selection| selection.value().unwrap()).collect();
+                values.sort_unstable();
+                values.dedup();
+                let selector = Selector::new(bank, rules);
+                let value = |selection: Option<Selection>| selection.map(|selection| selection.value().unwrap());
+
+                assert_eq!(value(selector.best(Order::Largest)), values.last().copied(), "{rules:?} on {bank:?}");
+                assert_eq!(value(selector.best(Order::Smallest)), values.first().copied(), "{rules:?} on {bank:?}");
+                let top: Vec<u128> = selector.top(Order::Largest, 5).into_iter().map(|selection| selection.value().unwrap()).collect();
+                assert_eq!(top, values.iter().rev().take(5).copied().collect::<Vec<_>>(), "{rules:?} on {bank:?}");
+            }
+
+            assert_eq!(Selector::new(bank, Rules::new(count)).best(Order::Largest), max_selection(bank, count));
+        }
+    }
+
+    #[test]
+    fn variants_test() {
+        let bank = input_generator("818181911112111").unwrap().remove(0);
+        assert_eq!(min_selection(&bank, 4).unwrap().to_string(), "1111");
+        assert_eq!(max_with_gap(&bank, 3, 2).unwrap().indices, [6, 11, 13]);
+        assert_eq!(max_divisible(&bank, 3, 7).unwrap().to_string(), "889");
+        assert_eq!(max_divisible(&bank, 2, 5), None);
+        assert_eq!(max_with_gap(&bank, 9, 2), None);
+        let top: Vec<String> = top_selections(&bank, 2, 4).iter().map(Selection::to_string).collect();
+        assert_eq!(top, ["92", "91", "89", "88"]);
+    }
+
+    #[test]
+    fn long_selection_test() {
+        // Forty nines don't fit in a u128, let alone a usize.
+        let input = vec![vec![9; 45], vec![1; 40]];
+        assert_eq!(max_selection(&input[0], 40).unwrap().value(), None);
+        assert_eq!(max_selection(&input[0], 38).unwrap().value(), Some(10_u128.pow(38) - 1));
+        assert_eq!(
+            solve_part2_with(&input, &Params { batteries: 40 }),
+            Answer::Text(format!("1{}0", "1".repeat(39)))
+        );
     }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::collections::BTreeMap;
 use rayon::prelude::*;
 use std::sync::{
     Arc, Mutex,
@@ -11,24 +12,81 @@ pub enum Map {
     Paper,
 }
 
+/// Which cells count as a roll's neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// All eight surrounding cells, as in the puzzle.
+    Eight,
+    /// Only the four orthogonal ones.
+    Four,
+}
+
+impl Neighbourhood {
+    fn of<T: Clone + Copy + PartialEq>(&self, grid: &Grid<T>, pos: &(usize, usize)) -> Vec<(usize, usize)> {
+        match self {
+            Neighbourhood::Eight => grid.neighbours_cando(pos),
+            Neighbourhood::Four  => grid.neighbours_ortho(pos),
+        }
+    }
+
+    /// The neighbours of `pos` that are rolls of paper.
+    fn rolls(&self, grid: &Grid<Map>, pos: &(usize, usize)) -> Vec<(usize, usize)> {
+        match self {
+            Neighbourhood::Eight => grid.neighbours_cando_as(pos, Map::Paper),
+            Neighbourhood::Four  => grid.neighbours_ortho_as(pos, Map::Paper),
+        }
+    }
+}
+
+impl FromField for Neighbourhood {
+    fn from_field(field: &str) -> Result<Self, String> {
+        match field {
+            "8" => Ok(Neighbourhood::Eight),
+            "4" => Ok(Neighbourhood::Four),
+            _   => Err("`8` or `4`".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// A forklift can reach rolls with fewer than this many neighbouring rolls.
+    pub threshold: u8,
+    pub neighbours: Neighbourhood,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { threshold: 4, neighbours: Neighbourhood::Eight }
+    }
+}
+
+params!(Params { threshold, neighbours });
+
 #[aoc_generator(day4)]
-pub fn input_generator(input: &str) -> Grid<Map> {
-    let width = input.lines().next().unwrap().len();
+pub fn input_generator(input: &str) -> Result<Grid<Map>, ParseError> {
+    let width = input.lines().next().ok_or(ParseError::missing("a grid").on_day(4))?.trim_end().len();
     let height = input.lines().count();
     let mut entity = Vec::new();
 
-    for line in input.lines() {
-        for ch in line.chars() {
+    for (row, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        for (col, ch) in line.chars().enumerate() {
             let map_item = match ch {
                 '.' => Map::Floor,
                 '@' => Map::Paper,
-                _ => unreachable!(),
+                _ => return Err(ParseError::new(line, col + 1, "`.` or `@`", ch).at_line(row + 1).on_day(4)),
             };
             entity.push(map_item);
         }
+
+        if line.len() != width {
+            let expected = format!("{width} columns");
+            return Err(ParseError::new(line, line.len() + 1, expected, line.len().to_string()).at_line(row + 1).on_day(4));
+        }
     }
 
-    Grid::new(width, height, entity)
+    Ok(Grid::new(width, height, entity))
 }
 
 #[allow(unused)]
@@ -41,12 +99,168 @@ fn draw_map(map: &Grid<Map>) {
     map.draw_enum_map(&char_map);
 }
 
+/// Rolls of paper next to each roll, 0 for the floor.
+fn neighbour_counts(input: &Grid<Map>, neighbours: Neighbourhood) -> Grid<u8> {
+    let mut counts = Grid::new_fill(input.width, input.height, 0);
+    for row in 0..input.height {
+        for col in 0..input.width {
+            if input[(col, row)] == Map::Paper {
+                counts[(col, row)] = neighbours.of(input, &(col, row)).iter().filter(|&&pos| input[pos] == Map::Paper).count() as u8;
+            }
+        }
+    }
+
+    counts
+}
+
+/// Removes rolls in waves, each taking every roll that was reachable once
+/// the wave before had gone. Returns the wave each roll went in, from 1, or
+/// `None` for rolls that stay and for the floor.
+///
+/// Only the neighbours of removed rolls can change, so rather than looking
+/// at every roll each wave, their neighbour counts are kept and counted
+/// down, and a roll joins the next wave when its count drops below the
+/// threshold.
+pub fn erode(input: &Grid<Map>, params: &Params) -> Grid<Option<u32>> {
+    let mut counts = neighbour_counts(input, params.neighbours);
+    let mut waves = Grid::new_fill(input.width, input.height, None);
+    let mut wave: Vec<(usize, usize)> = (0..input.height)
+        .flat_map(|row| (0..input.width).map(move |col| (col, row)))
+        .filter(|&pos| input[pos] == Map::Paper && counts[pos] < params.threshold)
+        .collect();
+    let mut number = 1;
+
+    while !wave.is_empty() {
+        let mut next = Vec::new();
+        for pos in &wave {
+            waves[*pos] = Some(number);
+        }
+        for pos in &wave {
+            for neighbour in params.neighbours.of(input, pos) {
+                if input[neighbour] == Map::Paper && waves[neighbour].is_none() {
+                    counts[neighbour] -= 1;
+                    // Rolls already under the threshold are in this wave.
+                    if counts[neighbour] + 1 == params.threshold {
+                        next.push(neighbour);
+                    }
+                }
+            }
+        }
+        wave = next;
+        number += 1;
+    }
+
+    waves
+}
+
+/// How many rolls went in each wave.
+pub fn wave_histogram(waves: &Grid<Option<u32>>) -> BTreeMap<u32, usize> {
+    let mut counts = BTreeMap::new();
+    for wave in waves.entity.iter().flatten() {
+        *counts.entry(*wave).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+/// The rolls that are never removed.
+pub fn stable_core(input: &Grid<Map>, waves: &Grid<Option<u32>>) -> Grid<bool> {
+    let core = input.entity.iter().zip(&waves.entity).map(|(&cell, wave)| cell == Map::Paper && wave.is_none()).collect();
+
+    Grid::new(input.width, input.height, core)
+}
+
+/// Groups of core rolls that touch through the neighbourhood, each in
+/// reading order, the groups ordered by their first roll.
+pub fn components(core: &Grid<bool>, neighbours: Neighbourhood) -> Vec<Vec<(usize, usize)>> {
+    let mut seen = Grid::new_fill(core.width, core.height, false);
+    let mut components = Vec::new();
+
+    for row in 0..core.height {
+        for col in 0..core.width {
+            if !core[(col, row)] || seen[(col, row)] {
+                continue;
+            }
+            seen[(col, row)] = true;
+            let mut component = vec![(col, row)];
+            let mut queue = VecDeque::from([(col, row)]);
+            while let Some(pos) = queue.pop_front() {
+                for next in neighbours.of(core, &pos) {
+                    if core[next] && !seen[next] {
+                        seen[next] = true;
+                        component.push(next);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            component.sort_unstable_by_key(|&(col, row)| (row, col));
+            components.push(component);
+        }
+    }
+
+    components
+}
+
+/// A wave as one character: 1 to 9, then a to z, then `+`.
+fn wave_char(wave: u32) -> char {
+    match wave {
+        1..=9   => char::from_digit(wave, 10).unwrap(),
+        10..=35 => char::from_digit(wave, 36).unwrap(),
+        _       => '+',
+    }
+}
+
+/// A map of the wave each roll went in, how many went each wave, and the
+/// rolls that stay, as a map and as connected groups.
+pub fn erosion_report(input: &Grid<Map>, params: &Params) -> String {
+    let waves = erode(input, params);
+    let cells = input.entity.iter().zip(&waves.entity).map(|(&cell, &wave)| match (cell, wave) {
+        (Map::Floor, _)    => '.',
+        (_, Some(wave))    => wave_char(wave),
+        (Map::Paper, None) => '@',
+    });
+    let mut report = String::from("Wave each roll was removed in, from 1 to 9, then a to z, then +. Rolls that stay are @.\n\n");
+    report.push_str(&Grid::new(input.width, input.height, cells.collect()).render(|&ch| ch));
+
+    let mut remaining = input.entity.iter().filter(|&&cell| cell == Map::Paper).count();
+    report.push_str("\n| Wave | Removed | Remaining |\n");
+    report.push_str("|-----:|--------:|----------:|\n");
+    for (wave, removed) in wave_histogram(&waves) {
+        remaining -= removed;
+        report.push_str(&format!("| {wave:>4} | {removed:>7} | {remaining:>9} |\n"));
+    }
+
+    let core = stable_core(input, &waves);
+    let groups = components(&core, params.neighbours);
+    let plural = if groups.len() == 1 { "" } else { "s" };
+    report.push_str(&format!("\nStable core: {remaining} rolls in {} group{plural}\n\n", groups.len()));
+    if groups.is_empty() {
+        return report;
+    }
+    report.push_str(&core.render(|&stays| if stays { '@' } else { '.' }));
+
+    report.push_str("\n| Group | Rolls | First x,y |\n");
+    report.push_str("|------:|------:|----------:|\n");
+    for (idx, group) in groups.iter().enumerate() {
+        let (col, row) = group[0];
+        report.push_str(&format!("| {:>5} | {:>5} | {:>9} |\n", idx + 1, group.len(), format!("{col},{row}")));
+    }
+
+    report
+}
+
 #[aoc(day4, part1)]
 pub fn solve_part1(input: &Grid<Map>) -> usize {
+    solve_part1_with(input, &Params::default())
+}
+
+pub fn solve_part1_with(input: &Grid<Map>, params: &Params) -> usize {
+    let counts = neighbour_counts(input, params.neighbours);
+
     (0..input.height).into_par_iter()
         .map(|row| {
-            (0..input.width).into_iter()
-                .filter(|&col| input[(col, row)] == Map::Paper && input.neighbours_cando_as(&(col, row), Map::Paper).len() < 4)
+            (0..input.width)
+                .filter(|&col| input[(col, row)] == Map::Paper && counts[(col, row)] < params.threshold)
                 .count()
         })
         .sum()
@@ -54,6 +268,19 @@ pub fn solve_part1(input: &Grid<Map>) -> usize {
 
 #[aoc(day4, part2)]
 pub fn solve_part2(input: &Grid<Map>) -> usize {
+    solve_part2_with(input, &Params::default())
+}
+
+pub fn solve_part2_with(input: &Grid<Map>, params: &Params) -> usize {
+    erode(input, params).entity.iter().filter(|wave| wave.is_some()).count()
+}
+
+#[aoc(day4, part2, Rounds)]
+pub fn solve_part2_rounds(input: &Grid<Map>) -> usize {
+    solve_part2_rounds_with(input, &Params::default())
+}
+
+pub fn solve_part2_rounds_with(input: &Grid<Map>, params: &Params) -> usize {
     let mut map = input.clone();
     let mut removeables = true;
     let sum = Arc::new(AtomicUsize::new(0));
@@ -63,7 +290,7 @@ pub fn solve_part2(input: &Grid<Map>) -> usize {
         (0..map.height).into_par_iter().for_each(|row| {
             let mut removal_inner = Vec::new();
             (0..map.width).into_iter().for_each(|col| {
-                if map[(col, row)] == Map::Paper && map.neighbours_cando_as(&(col, row), Map::Paper).len() < 4 {
+                if map[(col, row)] == Map::Paper && params.neighbours.rolls(&map, &(col, row)).len() < params.threshold as usize {
                     sum.fetch_add(1, Ordering::Relaxed);
                     removal_inner.push((col, row));
                 }
@@ -86,6 +313,10 @@ pub fn solve_part2(input: &Grid<Map>) -> usize {
 
 #[aoc(day4, part2, Alternative)]
 pub fn solve_part2_alternative(input: &Grid<Map>) -> usize {
+    solve_part2_alternative_with(input, &Params::default())
+}
+
+pub fn solve_part2_alternative_with(input: &Grid<Map>, params: &Params) -> usize {
     let mut map = input.clone();
     let mut queue = Vec::new();
     let mut next = HashSet::new();
@@ -94,8 +325,8 @@ pub fn solve_part2_alternative(input: &Grid<Map>) -> usize {
 
     for row in 0..input.height {
         for col in 0..input.width {
-            if input[(row, col)] == Map::Paper && map.neighbours_cando_count(&(row, col), Map::Paper) < 4 {
-                queue.push((row, col));
+            if input[(col, row)] == Map::Paper && params.neighbours.rolls(&map, &(col, row)).len() < params.threshold as usize {
+                queue.push((col, row));
             }
         }
     }
@@ -104,8 +335,8 @@ pub fn solve_part2_alternative(input: &Grid<Map>) -> usize {
         let mut removal = Vec::new();
 
         for pos in &queue {
-            let neighbours = map.neighbours_cando_as(pos, Map::Paper);
-            if map[*pos] == Map::Paper && neighbours.len() < 4 {
+            let neighbours = params.neighbours.rolls(&map, pos);
+            if map[*pos] == Map::Paper && neighbours.len() < params.threshold as usize {
                 removal.push(pos);
                 next.extend(neighbours);
                 sum += 1;
@@ -125,9 +356,42 @@ pub fn solve_part2_alternative(input: &Grid<Map>) -> usize {
     sum
 }
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+
+    type Input = Grid<Map>;
+    type Params = Params;
+
+    fn parse(input: &str, _params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input, ctx: &Context<Params>) -> Answer {
+        solve_part1_with(input, ctx.params()).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, ctx| solve_part2_with(input, ctx.params()).into())
+    }
+
+    fn trace(input: &Self::Input, ctx: &Context<Params>) -> Option<String> {
+        Some(erosion_report(input, ctx.params()))
+    }
+
+    fn alternatives() -> Vec<(u32, &'static str, DaySolver<Self>)> {
+        vec![
+            (2, "Rounds", |input, ctx| solve_part2_rounds_with(input, ctx.params()).into()),
+            (2, "Alternative", |input, ctx| solve_part2_alternative_with(input, ctx.params()).into()),
+        ]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate::{Generator, PaperGrid};
 
     const TEST: &str = "..@@.@@@@.
 @@@.@.@.@@
@@ -142,16 +406,115 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 13);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 13);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 43);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), 43);
     }
 
     #[test]
     fn part2_test_alt() {
-        assert_eq!(solve_part2_alternative(&input_generator(TEST)), 43);
+        assert_eq!(solve_part2_alternative(&input_generator(TEST).unwrap()), 43);
+    }
+
+    #[test]
+    fn part2_test_rounds() {
+        assert_eq!(solve_part2_rounds(&input_generator(TEST).unwrap()), 43);
+    }
+
+    #[test]
+    fn non_square_test() {
+        let input = input_generator(&TEST.lines().take(4).collect::<Vec<_>>().join("\n")).unwrap();
+        assert_eq!(solve_part2_alternative(&input), solve_part2(&input));
+        assert_eq!(solve_part2_rounds(&input), solve_part2(&input));
+    }
+
+    /// Removes every reachable roll at once, over and over, counting from
+    /// scratch each round.
+    fn rounds(input: &Grid<Map>, params: &Params) -> Grid<Option<u32>> {
+        let mut map = input.clone();
+        let mut waves = Grid::new_fill(input.width, input.height, None);
+        for number in 1.. {
+            let counts = neighbour_counts(&map, params.neighbours);
+            let removed: Vec<(usize, usize)> = (0..map.height)
+                .flat_map(|row| (0..map.width).map(move |col| (col, row)))
+                .filter(|&pos| map[pos] == Map::Paper && counts[pos] < params.threshold)
+                .collect();
+            if removed.is_empty() {
+                break;
+            }
+            map.place_at(&removed, Map::Floor);
+            waves.place_at(&removed, Some(number));
+        }
+
+        waves
+    }
+
+    #[test]
+    fn erode_test() {
+        let input = input_generator(TEST).unwrap();
+        let waves = erode(&input, &Params::default());
+        assert_eq!(waves.entity.iter().filter(|&&wave| wave == Some(1)).count(), 13);
+        assert_eq!(waves[(2_usize, 0)], Some(1));
+        assert_eq!(waves[(0_usize, 0)], None);
+
+        for seed in 0..20 {
+            let input = input_generator(&PaperGrid { width: 12 + seed as usize % 5, height: 9, density: 0.7 }.generate_seeded(seed)).unwrap();
+            for threshold in [0, 2, 4, 5, 9] {
+                for neighbours in [Neighbourhood::Eight, Neighbourhood::Four] {
+                    let params = Params { threshold, neighbours };
+                    assert_eq!(erode(&input, &params).entity, rounds(&input, &params).entity, "seed {seed}, {params:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn report_test() {
+        let input = input_generator(TEST).unwrap();
+        let waves = erode(&input, &Params::default());
+        let histogram = wave_histogram(&waves);
+        assert_eq!(histogram[&1], 13);
+        assert_eq!(histogram.values().sum::<usize>(), 43);
+
+        let core = stable_core(&input, &waves);
+        let groups = components(&core, Neighbourhood::Eight);
+        assert_eq!(groups.iter().map(Vec::len).sum::<usize>(), core.entity.iter().filter(|&&stays| stays).count());
+        assert_eq!(groups.iter().map(Vec::len).sum::<usize>() + 43, input.entity.iter().filter(|&&cell| cell == Map::Paper).count());
+
+        let report = erosion_report(&input, &Params::default());
+        let map: Vec<&str> = report.lines().skip(2).take(10).collect();
+        assert_eq!(map[0], "..11.1121.");
+        assert!(report.contains("|    1 |      13 |"));
+        assert!(report.contains(&format!("Stable core: {} rolls in 1 group\n", core.entity.iter().filter(|&&stays| stays).count())));
+        assert_eq!(groups.len(), 1);
+    }
+
+    #[test]
+    fn components_test() {
+        let core = Grid::new(4, 3, "@..@.@..@..@".chars().map(|ch| ch == '@').collect());
+        let groups = components(&core, Neighbourhood::Eight);
+        assert_eq!(groups, [vec![(0, 0), (1, 1), (0, 2)], vec![(3, 0)], vec![(3, 2)]]);
+        assert_eq!(components(&core, Neighbourhood::Four).len(), 5);
+        assert_eq!(core.render(|&stays| if stays { '#' } else { '.' }), "#..#\n.#..\n#..#\n");
+    }
+
+    #[test]
+    fn params_test() {
+        let input = input_generator(TEST).unwrap();
+        let params: Params = crate::params::from_settings(&[("neighbours".to_string(), "4".to_string())]).unwrap();
+        assert_eq!(params.neighbours, Neighbourhood::Four);
+        assert!(solve_part2_with(&input, &params) > solve_part2(&input));
+        assert_eq!(solve_part1_with(&input, &Params { threshold: 9, ..Params::default() }), input.entity.iter().filter(|&&cell| cell == Map::Paper).count());
+
+        for threshold in [0, 2, 3, 5, 9] {
+            for neighbours in [Neighbourhood::Eight, Neighbourhood::Four] {
+                let params = Params { threshold, neighbours };
+                assert_eq!(solve_part2_rounds_with(&input, &params), solve_part2_with(&input, &params), "{params:?}");
+                assert_eq!(solve_part2_alternative_with(&input, &params), solve_part2_with(&input, &params), "{params:?}");
+            }
+        }
     }
 }
\ No newline at end of file
@@ -1,53 +1,206 @@
-use btree_range_map::{AsRange, RangeSet};
-use std::ops::Bound::Included;
+use crate::prelude::*;
 
-type Fresh = RangeSet<usize>;
+type Fresh = IntervalSet<usize>;
 type IDs = Vec<usize>;
 
-#[aoc_generator(day5)]
-pub fn input_generator(input: &str) -> (Fresh, IDs) {
-    let (input_range, input_available) = input.split_once("\n\n").unwrap();
-
-    (
-        input_range
-            .lines()
-            .filter_map(|line| {
-                let (start, end) = line.split_once('-').unwrap();
-                Some(start.parse().unwrap()..=end.parse().unwrap())
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    /// IDs to report on with `--trace`: which ranges cover each of them.
+    pub audit: Vec<usize>,
+}
+
+params!(Params { audit });
+
+/// A fresh range as the input gives it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreshRange {
+    /// Line in the input, from 1.
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl FreshRange {
+    pub fn contains(&self, id: usize) -> bool {
+        self.start <= id && id <= self.end
+    }
+}
+
+/// The fresh ranges, both as given and merged, and the available IDs.
+#[derive(Debug, Clone)]
+pub struct Database {
+    pub ranges: Vec<FreshRange>,
+    pub fresh: Fresh,
+    pub available: IDs,
+}
+
+/// A stretch of IDs that the same number of ranges cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Depth {
+    pub start: usize,
+    pub end: usize,
+    /// How many ranges cover each ID in the stretch.
+    pub ranges: usize,
+}
+
+impl Database {
+    /// The ranges that cover `id`, in input order.
+    pub fn covering(&self, id: usize) -> Vec<&FreshRange> {
+        self.ranges.iter().filter(|range| range.contains(id)).collect()
+    }
+
+    /// Every stretch covered by at least one range, split wherever the
+    /// number of ranges covering it changes.
+    pub fn depths(&self) -> Vec<Depth> {
+        let mut starts: Vec<usize> = self.ranges.iter().map(|range| range.start).collect();
+        let mut ends: Vec<usize> = self.ranges.iter().map(|range| range.end).collect();
+        starts.sort_unstable();
+        ends.sort_unstable();
+
+        // The count only changes where a range starts or just after one ends.
+        let mut bounds: Vec<usize> = starts.iter().copied().chain(ends.iter().filter_map(|end| end.checked_add(1))).collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        bounds
+            .iter()
+            .enumerate()
+            .map(|(idx, &start)| Depth {
+                start,
+                end: bounds.get(idx + 1).map_or(usize::MAX, |next| next - 1),
+                ranges: starts.partition_point(|&other| other <= start) - ends.partition_point(|&other| other < start),
             })
-            .collect(),
-        input_available
-            .lines()
-            .map(|line| line.parse().unwrap())
+            .filter(|depth| depth.ranges > 0)
+            .collect()
+    }
+
+    /// The first stretch where the most ranges overlap.
+    pub fn deepest(&self) -> Option<Depth> {
+        self.depths().into_iter().rev().max_by_key(|depth| depth.ranges)
+    }
+
+    /// Ranges that every ID of is covered by some other range as well, so
+    /// that leaving any one of them out wouldn't change what's fresh. Two
+    /// copies of a range are both redundant, though only one can go.
+    pub fn redundant(&self) -> Vec<&FreshRange> {
+        let overlapped: Fresh = self
+            .depths()
+            .into_iter()
+            .filter(|depth| depth.ranges > 1)
+            .map(|depth| depth.start..=depth.end)
+            .collect();
+
+        self.ranges
+            .iter()
+            .filter(|range| Fresh::from_iter([range.start..=range.end]).difference(&overlapped).is_empty())
             .collect()
-    )
+    }
 }
 
-#[aoc(day5, part1)]
-pub fn solve_part1((fresh, available): &(Fresh, IDs)) -> usize {
-    let mut sum = 0;
+#[aoc_generator(day5)]
+pub fn input_generator(input: &str) -> Result<Database, ParseError> {
+    let sections = sections(input);
+    let [(range_start, input_range), (available_start, input_available)] = sections[..] else {
+        return Err(ParseError::missing("two sections separated by a blank line").on_day(5));
+    };
 
-    for &item in available {
-        if fresh.contains(item) {
-            sum += 1;
+    let ranges: Vec<FreshRange> = parse_lines!(input_range, "{}-{}" => usize, usize)
+        .map_err(|err| err.shift_lines(range_start).on_day(5))?
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (start, end))| FreshRange { line: range_start + idx + 1, start, end })
+        .collect();
+
+    // A range running backwards would have no IDs, and would throw the depth
+    // count off.
+    for (range, line) in ranges.iter().zip(input_range.lines()) {
+        if range.start > range.end {
+            let line = line.trim_end();
+            let column = line.find('-').unwrap() + 2;
+            let expected = format!("an end of at least {}", range.start);
+            return Err(ParseError::new(line, column, expected, &line[column - 1..]).at_line(range.line).on_day(5));
         }
     }
 
-    sum
+    Ok(Database {
+        fresh: ranges.iter().map(|range| range.start..=range.end).collect(),
+        ranges,
+        available: parse_lines!(input_available, "{}" => usize)
+            .map_err(|err| err.shift_lines(available_start).on_day(5))?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect(),
+    })
+}
+
+#[aoc(day5, part1)]
+pub fn solve_part1(input: &Database) -> usize {
+    let mut available = input.available.clone();
+    available.sort_unstable();
+
+    input.fresh.contains_sorted(&available).into_iter().filter(|&fresh| fresh).count()
 }
 
 #[aoc(day5, part2)]
-pub fn solve_part2((fresh, _): &(Fresh, IDs)) -> usize {
-    let mut sum = 0;
+pub fn solve_part2(input: &Database) -> usize {
+    input.fresh.count()
+}
+
+/// The ranges covering each audited ID, where the most ranges overlap, and
+/// the ranges that could go without changing anything.
+pub fn audit_report(input: &Database, params: &Params) -> String {
+    let lines = |ranges: &[&FreshRange]| ranges.iter().map(|range| range.line.to_string()).collect::<Vec<_>>().join(", ");
+    let mut report = String::new();
+
+    if !params.audit.is_empty() {
+        report.push_str("|               ID | Fresh | Ranges | Lines                |\n");
+        report.push_str("|-----------------:|:------|-------:|:---------------------|\n");
+        for &id in &params.audit {
+            let covering = input.covering(id);
+            let fresh = if covering.is_empty() { "no" } else { "yes" };
+            report.push_str(&format!("| {id:>16} | {fresh:<5} | {:>6} | {:<20} |\n", covering.len(), lines(&covering)));
+        }
+        report.push('\n');
+    }
+
+    match input.deepest() {
+        Some(depth) => report.push_str(&format!("Most overlap: {} ranges, over {}-{}\n", depth.ranges, depth.start, depth.end)),
+        None        => report.push_str("No fresh ranges\n"),
+    }
+
+    let redundant = input.redundant();
+    if redundant.is_empty() {
+        report.push_str("No redundant ranges\n");
+    } else {
+        report.push_str(&format!("Redundant ranges on lines: {}\n", lines(&redundant)));
+    }
+
+    report
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+
+    type Input = Database;
+    type Params = Params;
+
+    fn parse(input: &str, _params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
 
-    for range in fresh {
-        sum += match (range.start(), range.end()) {
-            (Included(start_val), Included(end_val)) => (*start_val..=*end_val).count(),
-            _ => panic!("Expected included start and end bounds!"),
-        };
+    fn part1(input: &Self::Input, _ctx: &Context<Params>) -> Answer {
+        solve_part1(input).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, _ctx| solve_part2(input).into())
+    }
+
+    fn trace(input: &Self::Input, ctx: &Context<Params>) -> Option<String> {
+        Some(audit_report(input, ctx.params()))
     }
-    
-    sum
 }
 
 #[cfg(test)]
@@ -68,11 +221,48 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 3);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 3);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 14);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), 14);
+    }
+
+    #[test]
+    fn audit_test() {
+        let input = input_generator(TEST).unwrap();
+        assert_eq!(input.ranges[3], FreshRange { line: 4, start: 12, end: 18 });
+        assert_eq!(input.covering(17).iter().map(|range| range.line).collect::<Vec<_>>(), [3, 4]);
+        assert!(input.covering(8).is_empty());
+        assert_eq!(input.deepest(), Some(Depth { start: 12, end: 14, ranges: 2 }));
+        assert!(input.redundant().is_empty());
+
+        let depths: usize = input.depths().iter().map(|depth| (depth.end - depth.start + 1) * depth.ranges).sum();
+        assert_eq!(depths, input.ranges.iter().map(|range| range.end - range.start + 1).sum());
+
+        let input = input_generator("1-10\n2-3\n8-12\n5-9\n20-25\n20-25\n\n1").unwrap();
+        assert_eq!(input.redundant().iter().map(|range| range.line).collect::<Vec<_>>(), [2, 4, 5, 6]);
+        assert_eq!(input.deepest(), Some(Depth { start: 8, end: 9, ranges: 3 }));
+
+        let report = audit_report(&input, &Params { audit: vec![8, 15] });
+        assert!(report.contains("|                8 | yes   |      3 | 1, 3, 4              |"));
+        assert!(report.contains("|               15 | no    |      0 |                      |"));
+        assert!(report.contains("Most overlap: 3 ranges, over 8-9"));
+        assert!(report.contains("Redundant ranges on lines: 2, 4, 5, 6"));
+    }
+
+    #[test]
+    fn wide_range_test() {
+        let input = input_generator("1-1000000000000000\n500-2000000000000000\n\n0\n1999999999999999").unwrap();
+        assert_eq!(solve_part1(&input), 1);
+        assert_eq!(solve_part2(&input), 2_000_000_000_000_000);
+    }
+
+    #[test]
+    fn reversed_range_test() {
+        let err = input_generator("3-5\n10-5\n\n1").unwrap_err();
+        assert_eq!((err.day, err.line, err.column, err.found.as_str()), (5, 2, 4, "5"));
+        assert!(input_generator("5-5\n\n1").is_ok());
     }
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
+use crate::prelude::*;
 use ndarray::{Array2, Axis};
-use std::collections::HashSet;
 
 // pub struct Sheet {
 //     numbers: Vec<Vec<usize>>,
@@ -19,24 +19,20 @@ impl Block {
     }
 
     fn calc(&self, axis: usize) -> usize {
-        let mut sum = 0;
+        let mut sum = if self.oper == '*' { 1 } else { 0 };
 
         for view in self.array.axis_iter(Axis(axis)) {
             let number = view
                 .into_iter()
                 .filter_map(|&option_ref| option_ref)
                 .collect::<String>()
-                .parse()
+                .parse::<usize>()
                 .unwrap();
 
-            if sum == 0 && self.oper == '*' {
-                sum = number;
-            } else {
-                match self.oper {
-                    '+' => sum += number,
-                    '*' => sum *= number,
-                    _   => unreachable!("There should not be any other operands.")
-                }
+            match self.oper {
+                '+' => sum += number,
+                '*' => sum *= number,
+                _   => unreachable!("There should not be any other operands.")
             }
         }
 
@@ -70,13 +66,13 @@ impl Block {
 
 //     Sheet { numbers: sheet, operands: char_vec }
 // }
-pub fn input_generator(input: &str) -> Vec<Block> {
+pub fn input_generator(input: &str) -> Result<Vec<Block>, ParseError> {
     let mut operands = Vec::new();
     let mut gaps = HashSet::new();
     let mut lines = input.lines();
     let mut blocks = Vec::new();
 
-    let last_line = lines.next_back().unwrap_or("");
+    let last_line = lines.next_back().ok_or(ParseError::missing("a row of operands").on_day(6))?;
 
     // Array dimensions
     let mut widths = Vec::new();
@@ -87,6 +83,12 @@ pub fn input_generator(input: &str) -> Vec<Block> {
     // reduce the overhead.
     let mut width = 0;
     for (idx, ch) in last_line.chars().enumerate() {
+        let valid = if idx == 0 { ch == '+' || ch == '*' } else { ch == '+' || ch == '*' || ch == ' ' };
+        if !valid {
+            let expected = if idx == 0 { "`+` or `*`" } else { "`+`, `*` or a space" };
+            return Err(ParseError::new(last_line, idx + 1, expected, ch).at_line(height + 1).on_day(6));
+        }
+
         if ch == '+' || ch == '*' {
             operands.push(ch);
             if idx > 0 {
@@ -109,19 +111,29 @@ pub fn input_generator(input: &str) -> Vec<Block> {
         let mut block_num = 0;
         let mut col_num = 0;
         for (idx, ch) in line.chars().enumerate() {
+            let fail = |expected| Err(ParseError::new(line, idx + 1, expected, ch).at_line(row + 1).on_day(6));
+
             if gaps.contains(&idx) {
+                if ch != ' ' {
+                    return fail("a space between problems");
+                }
                 block_num += 1;
                 col_num = 0;
             } else {
-                if ch != ' ' {
+                if col_num >= widths[block_num] {
+                    return fail("the end of the line (it is longer than the operand row)");
+                }
+                if ch.is_ascii_digit() {
                     blocks[block_num].array[(row, col_num)] = Some(ch);
+                } else if ch != ' ' {
+                    return fail("a digit or a space");
                 }
                 col_num += 1;
             }
         }
     }
 
-    blocks
+    Ok(blocks)
 }
 
 #[aoc(day6, part1)]
@@ -164,6 +176,27 @@ pub fn solve_part2(input: &Vec<Block>) -> usize {
     sum
 }
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+
+    type Input = Vec<Block>;
+    type Params = ();
+
+    fn parse(input: &str, _params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input, _ctx: &Context) -> Answer {
+        solve_part1(input).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, _ctx| solve_part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -175,11 +208,16 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 4277556);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 4277556);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 3263827);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), 3263827);
+    }
+
+    #[test]
+    fn zero_factor_test() {
+        assert_eq!(solve_part1(&input_generator("0 \n5 \n* ").unwrap()), 0);
     }
 }
\ No newline at end of file
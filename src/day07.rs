@@ -1,18 +1,31 @@
 use crate::prelude::*;
 
 #[aoc_generator(day7)]
-pub fn input_generator(input: &str) -> Vec<Vec<usize>> {
-    input
+pub fn input_generator(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let manifold: Vec<Vec<usize>> = input
         .lines()
         .enumerate()
         .filter(|(row, _)| row % 2 == 0)
-        .map(|(_, line)| {
+        .map(|(row, line)| {
+            let line = line.trim_end();
             line.chars()
                 .enumerate()
-                .filter_map(|(col, ch)| if ch != '.' { Some(col) } else { None })
+                .filter_map(|(col, ch)| match (row, ch) {
+                    (_, '.') => None,
+                    (0, 'S') | (1.., '^') => Some(Ok(col)),
+                    _ => {
+                        let expected = if row == 0 { "`.` or `S`" } else { "`.` or `^`" };
+                        Some(Err(ParseError::new(line, col + 1, expected, ch).at_line(row + 1).on_day(7)))
+                    },
+                })
                 .collect()
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    match manifold.first() {
+        Some(start) if start.len() == 1 => Ok(manifold),
+        _ => Err(ParseError::new(input.lines().next().unwrap_or(""), 1, "exactly one `S` on the first line", "none or several").at_line(1).on_day(7)),
+    }
 }
 
 #[aoc(day7, part1)]
@@ -64,6 +77,27 @@ pub fn solve_part2(input: &Vec<Vec<usize>>) -> usize {
     _split_time(input, 1, input[0][0], &mut cache)
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+
+    type Input = Vec<Vec<usize>>;
+    type Params = ();
+
+    fn parse(input: &str, _params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input, _ctx: &Context) -> Answer {
+        solve_part1(input).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, _ctx| solve_part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,11 +121,11 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 21);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 21);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 40);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), 40);
     }
 }
\ No newline at end of file
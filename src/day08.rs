@@ -3,17 +3,27 @@ use crate::prelude::*;
 type Distances = Vec<(usize, (usize, usize))>;
 type Points = Vec<Vec<isize>>;
 
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Shortest connections made in part 1. The example uses 10.
+    pub connections: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { connections: 1000 }
+    }
+}
+
+params!(Params { connections });
+
 #[aoc_generator(day8)]
-pub fn input_generator(input: &str) -> (Points, Distances) {
-    let points: Vec<Vec<isize>> = input
-        .lines()
-        .map(|line| line
-            .split(',')
-            .map(|part| part
-                .parse()
-                .unwrap()
-            ).collect()
-        ).collect();
+pub fn input_generator(input: &str) -> Result<(Points, Distances), ParseError> {
+    let points: Vec<Vec<isize>> = parse_lines!(input, "{},{},{}" => isize, isize, isize)
+        .map_err(|err| err.on_day(8))?
+        .into_iter()
+        .map(|(x, y, z)| vec![x, y, z])
+        .collect();
 
     // Sqrt dropped as it's not really necessary - the actual distance isn't
     // important, just need some way to order distances.
@@ -33,7 +43,7 @@ pub fn input_generator(input: &str) -> (Points, Distances) {
         }
     }
 
-    let size = input.lines().clone().count();
+    let size = points.len();
     let mut distance_set = HashSet::new();
     (0..size)
         .flat_map(|col| (col + 1..size).map(move |row| (row, col)))
@@ -45,7 +55,7 @@ pub fn input_generator(input: &str) -> (Points, Distances) {
     let mut distances: Vec<(usize, (usize, usize))> = distance_set.into_iter().collect();
     distances.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
 
-    (points, distances)
+    Ok((points, distances))
 }
 
 enum GroupErr {
@@ -56,7 +66,6 @@ enum GroupErr {
 trait GroupOperations {
     fn check(&mut self, coords: &(usize, usize)) -> Result<Vec<(usize, usize)>, GroupErr>;
     fn merge(&mut self, idx1: usize, idx2: usize);
-    fn singles(&self, points: &Points) -> Vec<usize>;
 }
 
 impl GroupOperations for Vec<HashSet<usize>> {
@@ -93,34 +102,19 @@ impl GroupOperations for Vec<HashSet<usize>> {
             current_set.insert(item);
         }
     }
-
-    fn singles(&self, points: &Points) -> Vec<usize> {
-        let mut singles = Vec::new();
-
-        for idx in 0..points.len() {
-            let mut contained = false;
-            for group in self {
-                if group.contains(&idx) {
-                    contained = true;
-                }
-            }
-
-            if !contained {
-                singles.push(idx);
-            }
-        }
-
-        singles
-    }
 }
 
 #[aoc(day8, part1)]
-pub fn solve_part1((_, distances): &(Points, Distances)) -> usize {
+pub fn solve_part1(input: &(Points, Distances)) -> usize {
+    solve_part1_with(input, &Params::default())
+}
+
+pub fn solve_part1_with((_, distances): &(Points, Distances), params: &Params) -> usize {
     let mut groups = Vec::new();
     let mut connections = 0;
     let mut idx = 0;
 
-    while connections < 1000 {
+    while connections < params.connections && idx < distances.len() {
         let (_, coords) = distances[idx];
         
         match groups.check(&coords) {
@@ -144,13 +138,10 @@ pub fn solve_part1((_, distances): &(Points, Distances)) -> usize {
         idx += 1;
     }
 
-    let mut lengths = Vec::new();
-    for group in groups {
-        lengths.push(group.len());
-    }
-    lengths.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    let mut lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+    lengths.sort_unstable_by(|a, b| b.cmp(a));
 
-    lengths.iter().take(3).fold(1, |acc, &x| acc * x)
+    lengths.iter().take(3).product()
 }
 
 // Passes test, but run = 6171742720 is too low
@@ -159,7 +150,6 @@ pub fn solve_part1((_, distances): &(Points, Distances)) -> usize {
 pub fn solve_part2((points, distances): &(Points, Distances)) -> usize {
     let mut groups = Vec::new();
     let mut idx = 0;
-    let mut mark = true;
 
     loop {
         let (_, coords) = distances[idx];
@@ -179,14 +169,8 @@ pub fn solve_part2((points, distances): &(Points, Distances)) -> usize {
             Err(GroupErr::SameGroup) => {},
         }
 
-        if groups.len() > 2 {
-            mark = false;
-        }
-
-        // Test for singles is needed for the test to pass. Release execution
-        // can use it, but runs faster without it.
-        if groups.len() == 1 && !mark && groups.singles(points).is_empty() {
-        // if groups.len() == 1 && !mark {
+        // Done once a single circuit holds every box.
+        if groups.len() == 1 && groups[0].len() == points.len() {
             break;
         }
         
@@ -198,6 +182,27 @@ pub fn solve_part2((points, distances): &(Points, Distances)) -> usize {
     (points[box_a][0] * points[box_b][0]) as usize
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+
+    type Input = (Points, Distances);
+    type Params = Params;
+
+    fn parse(input: &str, _params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input, ctx: &Context<Params>) -> Answer {
+        solve_part1_with(input, ctx.params()).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, _ctx| solve_part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,14 +228,18 @@ mod tests {
 984,92,344
 425,690,689";
 
-    // Change part 1 to run 10 connections to pass test.
-    // #[test]
-    // fn part1_test() {
-    //     assert_eq!(solve_part1(&input_generator(TEST)), 40);
-    // }
+    #[test]
+    fn part1_test() {
+        assert_eq!(solve_part1_with(&input_generator(TEST).unwrap(), &Params { connections: 10 }), 40);
+    }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 25272);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), 25272);
+    }
+
+    #[test]
+    fn two_boxes_test() {
+        assert_eq!(solve_part2(&input_generator("5,0,0\n7,1,1").unwrap()), 35);
     }
 }
\ No newline at end of file
@@ -1,111 +1,57 @@
 use crate::prelude::*;
 use rayon::prelude::*;
-use std::ops::Range;
-use std::sync::{Arc, Mutex};
 
 type Point = (usize, usize);
 type Points = Vec<(usize, usize)>;
 
+// Compression keeps a gap cell between coordinates that aren't adjacent, so
+// the compressed loop has the same shape as the real one.
 #[aoc_generator(day9)]
-pub fn input_generator(input: &str) -> (Points, Points) {
-    let mut x_unique = HashSet::new();
-    let mut y_unique = HashSet::new();
-
-    let points: Vec<Point> = input
+pub fn input_generator(input: &str) -> Result<(Points, Points), ParseError> {
+    // Each tile keeps the line it came from, for reporting errors.
+    let tiles: Vec<(usize, &str, Point)> = input
         .lines()
-        .flat_map(|line| {
-            let mut parts = line.split(',');
-            let (x, y) = (parts.next().unwrap().parse().unwrap(), parts.next().unwrap().parse().unwrap());
-            x_unique.insert(x);
-            y_unique.insert(y);
-            Some((x, y))
+        .enumerate()
+        .map(|(idx, line)| {
+            let line = line.trim_end();
+            parse_line!(line, "{},{}" => usize, usize)
+                .map(|point| (idx + 1, line, point))
+                .map_err(|err| err.at_line(idx + 1).on_day(9))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    let mut x_map: Vec<usize> = x_unique.into_iter().collect();
-    x_map.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-    let mut y_map: Vec<usize> = y_unique.into_iter().collect();
-    y_map.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-    
-    let mut compressed = Vec::new();
-    for (x, y) in points.iter() {
-        let x_idx = x_map.iter().position(|&pos_x| pos_x == *x).unwrap();
-        let y_idx = y_map.iter().position(|&pos_y| pos_y == *y).unwrap();
-        compressed.push((x_idx, y_idx));
+    if tiles.len() < 3 {
+        return Err(ParseError::missing("at least three tiles").on_day(9));
     }
 
-    (points, compressed)
-}
-
-fn calc_area(point_a: &Point, point_b: &Point) -> usize {
-    fn delta(a: usize, b: usize) -> usize {
-        if a > b {
-            return a - b + 1;
-        } else {
-            return b - a + 1;
-        }
+    // Every tile must share a row or column with the one before it, including
+    // the last wrapping round to the first, so the loop is a rectilinear
+    // polygon. A bad edge is reported at the tile it leads to.
+    let points: Vec<Point> = tiles.iter().map(|&(_, _, point)| point).collect();
+    let corners = points.iter().map(|&(x, y)| (x as i64, y as i64)).collect();
+    if let Err(PolygonError::NotAxisAligned(idx)) = Polygon::new_rectilinear(corners) {
+        let (_, _, prev) = tiles[idx];
+        let (line_no, line, _) = tiles[(idx + 1) % tiles.len()];
+        let expected = format!("a tile in line with {},{}", prev.0, prev.1);
+        return Err(ParseError::new(line, 1, expected, line).at_line(line_no).on_day(9));
     }
 
-    delta(point_a.0, point_b.0) * delta(point_a.1, point_b.1)
-}
-
-// Checks if any point is within the given rectangle
-fn bounding_box(perimeter: &HashSet<Point>, points: &Points, point_a: &usize, point_b: &usize) -> bool {
-    fn invalid_range(a: usize, b: usize) -> Range<usize> {
-        if a > b {
-            return (b + 1)..(a);
-        } else {
-            return (a + 1)..(b);
-        }
-    }
-
-    let point1 = points[*point_a];
-    let point2 = points[*point_b];
-
-    let invalid_x = invalid_range(point1.0, point2.0);
-    let invalid_y = invalid_range(point1.1, point2.1);
-
-    // This works, but is slow. It turns out using HashSets is even slower.
-    for point in perimeter {
-        if invalid_x.contains(&point.0) && invalid_y.contains(&point.1) {
-            return false;
-        }
-    }
-
-    true
-}
-
-fn generate_perimeter(input: &Points) -> HashSet<Point> {
-    let mut perimeter = HashSet::new();
+    let x_axis = CompressedAxis::with_gaps(points.iter().map(|&(x, _)| x));
+    let y_axis = CompressedAxis::with_gaps(points.iter().map(|&(_, y)| y));
 
-    fn connect_points((x1, y1): Point, (x2, y2): Point) -> Points {
-    let mut line = Vec::new();
-
-    let (start_x, end_x, start_y, end_y) = if x1 <= x2 {
-        (x1, x2, y1, y2)
-    } else {
-        (x2, x1, y2, y1)
-    };
-
-    if start_x == end_x {
-        for y in min(start_y, end_y)..=max(start_y, end_y) {
-            line.push((start_x, y));
-        }
-    } else {
-        for x in start_x..=end_x {
-            line.push((x, start_y));
-        }
-    }
+    let compressed = points
+        .iter()
+        .map(|&(x, y)| (x_axis.compress(x).unwrap(), y_axis.compress(y).unwrap()))
+        .collect();
 
-    line
+    Ok((points, compressed))
 }
 
-    for window in input.windows(2) {
-        perimeter.extend(connect_points(window[0], window[1]));
-    }
-    perimeter.extend(connect_points(*input.last().unwrap(), input[0]));
-
-    perimeter
+fn to_rect(point_a: &Point, point_b: &Point) -> Rect {
+    Rect::from_corners(
+        (point_a.0 as i64, point_a.1 as i64),
+        (point_b.0 as i64, point_b.1 as i64),
+    )
 }
 
 #[aoc(day9, part1)]
@@ -117,7 +63,7 @@ pub fn solve_part1((points, _): &(Points, Points)) -> usize {
         .flat_map(|point_a| (point_a + 1..size).map(move |point_b| (point_b, point_a)))
         .filter(|&(point_b, point_a)| point_b > point_a)
         .for_each(|(point_b, point_a)| {
-            let area = calc_area(&points[point_a], &points[point_b]);
+            let area = to_rect(&points[point_a], &points[point_b]).area() as usize;
             if area > largest {
                 largest = area;
             }
@@ -126,49 +72,109 @@ pub fn solve_part1((points, _): &(Points, Points)) -> usize {
     largest
 }
 
+/// Largest `area` over every pair of corners, or 0 if none has one. Each
+/// first corner's pairs are reported to the context's progress once done.
+fn largest_pair(size: usize, ctx: &Context, area: impl Fn(usize, usize) -> Option<usize> + Sync) -> usize {
+    let progress = ctx.progress();
+    progress.start("pairs", (size * size.saturating_sub(1) / 2) as u64);
+
+    let largest = (0..size)
+        .into_par_iter()
+        .filter_map(|point_a| {
+            let largest = (point_a + 1..size).into_par_iter().filter_map(|point_b| area(point_a, point_b)).max();
+            progress.advance((size - point_a - 1) as u64);
+            largest
+        })
+        .max()
+        .unwrap_or(0);
+
+    progress.finish();
+    largest
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Tile {
+    Unknown,
+    Loop,
+    Outside,
+}
+
+// Rasterises the compressed loop, floods the outside, then checks each
+// rectangle against a prefix sum of outside cells in O(1). Working in tiles
+// rather than treating the loop as a line means a notch between corners one
+// apart, which has no tiles in it, doesn't rule rectangles out.
 #[aoc(day9, part2)]
-// pub fn solve_part2((points, coods): &(Points, Coords)) -> usize {
-//     let size = points.len();
-//     let largest = Arc::new(Mutex::new(0));
-//     let perimeter = generate_perimeter(points);
-
-//     (0..size)
-//         .into_par_iter()
-//         .for_each(|point_a| {
-//             (point_a + 1..size).into_par_iter().for_each(|point_b| {
-//                 if bounding_box(&perimeter, points, &point_a, &point_b) {
-//                     let area = calc_area(&points[point_a], &points[point_b]);
-//                     let mut largest_inner = largest.lock().unwrap();
-//                     if area > *largest_inner {
-//                         *largest_inner = area;
-//                     }
-//                 }
-//             });
-//         });
-
-//     *largest.lock().unwrap()
-// }
-// This version uses coordinate compression. Now runs 200x faster! It breaks the test though.
-pub fn solve_part2((points, coords): &(Points, Points)) -> usize {
+pub fn solve_part2(input: &(Points, Points)) -> usize {
+    solve_part2_with(input, &Context::default())
+}
+
+pub fn solve_part2_with((points, coords): &(Points, Points), ctx: &Context) -> usize {
     let size = points.len();
-    let largest = Arc::new(Mutex::new(0));
-    let perimeter = generate_perimeter(&coords);
+    // Pad by one cell on each side so the flood can get all the way round.
+    let width = coords.iter().map(|&(x, _)| x).max().unwrap() + 3;
+    let height = coords.iter().map(|&(_, y)| y).max().unwrap() + 3;
+    let mut map = Grid::new_fill(width, height, Tile::Unknown);
+
+    for idx in 0..size {
+        for (x, y) in to_rect(&coords[idx], &coords[(idx + 1) % size]).points() {
+            map[(x as usize + 1, y as usize + 1)] = Tile::Loop;
+        }
+    }
 
-    (0..size)
-        .into_par_iter()
-        .for_each(|point_a| {
-            (point_a + 1..size).into_par_iter().for_each(|point_b| {
-                if bounding_box(&perimeter, &coords, &point_a, &point_b) {
-                    let area = calc_area(&points[point_a], &points[point_b]);
-                    let mut largest_inner = largest.lock().unwrap();
-                    if area > *largest_inner {
-                        *largest_inner = area;
-                    }
-                }
-            });
-        });
+    let mut queue = vec![(0, 0)];
+    map[(0usize, 0usize)] = Tile::Outside;
+    while let Some(pos) = queue.pop() {
+        for next in map.neighbours_ortho_as(&pos, Tile::Unknown) {
+            map[next] = Tile::Outside;
+            queue.push(next);
+        }
+    }
+
+    let mut outside = vec![0; (width + 1) * (height + 1)];
+    for y in 0..height {
+        for x in 0..width {
+            let cell = if map[(x, y)] == Tile::Outside { 1 } else { 0 };
+            outside[(y + 1) * (width + 1) + x + 1] = cell
+                + outside[y * (width + 1) + x + 1]
+                + outside[(y + 1) * (width + 1) + x]
+                - outside[y * (width + 1) + x];
+        }
+    }
+
+    // Outside cells in a compressed rectangle. The sums are one further on
+    // than the map, which is padded by one.
+    let outside_in = |rect: Rect| {
+        let (x0, y0) = (rect.min.0 as usize + 1, rect.min.1 as usize + 1);
+        let (x1, y1) = (rect.max.0 as usize + 2, rect.max.1 as usize + 2);
+        outside[y1 * (width + 1) + x1] + outside[y0 * (width + 1) + x0]
+            - outside[y0 * (width + 1) + x1] - outside[y1 * (width + 1) + x0]
+    };
 
-    *largest.lock().unwrap()
+    largest_pair(size, ctx, |point_a, point_b| {
+        (outside_in(to_rect(&coords[point_a], &coords[point_b])) == 0)
+            .then(|| to_rect(&points[point_a], &points[point_b]).area() as usize)
+    })
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+
+    type Input = (Points, Points);
+    type Params = ();
+
+    fn parse(input: &str, _params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input, _ctx: &Context) -> Answer {
+        solve_part1(input).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, ctx| solve_part2_with(input, ctx).into())
+    }
 }
 
 #[cfg(test)]
@@ -186,11 +192,27 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 50);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 50);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 24);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), 24);
+    }
+
+    // The notch at x = 24..25 is one tile wide on either side, so it has no
+    // tiles of its own and the 184 tile rectangle below it is valid.
+    #[test]
+    fn narrow_notch_test() {
+        let input = "12,26\n22,26\n22,25\n24,25\n24,23\n25,23\n25,24\n34,24\n34,11\n25,11\n25,6\n24,6\n24,16\n22,16\n22,17\n12,17";
+        assert_eq!(solve_part2(&input_generator(input).unwrap()), 184);
+    }
+
+    #[test]
+    fn error_test() {
+        let err = input_generator("7,1\n11,1\n11,7\n9,8\n7,8").unwrap_err();
+        assert_eq!((err.day, err.line, err.found.as_str()), (9, 4, "9,8"));
+        assert_eq!(input_generator("7,1\n11,1\n11,7\n8,7").unwrap_err().line, 1);
+        assert_eq!(input_generator("7,1\n11,x").unwrap_err().line, 2);
     }
 }
\ No newline at end of file
@@ -1,5 +1,4 @@
 use binarray::BinaryArray;
-use indicatif::{ProgressBar, ProgressStyle};
 use crate::prelude::*;
 use rayon::prelude::*;
 use z3::{Optimize, SatResult, ast::Int};
@@ -76,46 +75,52 @@ impl Machine {
 }
 
 #[aoc_generator(day10)]
-pub fn input_generator(input: &str) -> Vec<Machine> {
+pub fn input_generator(input: &str) -> Result<Vec<Machine>, ParseError> {
     let mut machines = Vec::new();
 
-    for line in input.lines() {
-        let mut parts: Vec<&str> = line.split_whitespace().collect();
-        let first = parts.remove(0);
+    for (row, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        let fail = |err: ParseError| err.at_line(row + 1).on_day(10);
+        let (lights, capsules, joltage) = parse_line!(line, "[{}] {} {{}}" => String, Vec<String>, Vec<usize>)
+            .map_err(fail)?;
 
         // Machine variables
         let mut target = 0_u16;
         let mut buttons = Vec::new();
-        let mut joltage = Vec::new();
 
-        for (idx, ch) in first[1..first.len()-1].chars().enumerate() {
-            if ch == '#' {
-                target.set_bit(idx, true);
+        if lights.len() > 16 {
+            return Err(fail(ParseError::new(line, 2, "at most 16 lights", lights.len().to_string())));
+        }
+        for (idx, ch) in lights.chars().enumerate() {
+            match ch {
+                '#' => target.set_bit(idx, true),
+                '.' => {},
+                _   => return Err(fail(ParseError::new(line, idx + 2, "`.` or `#`", ch))),
             }
         }
 
-        for part in parts {
-            // Split up each capsule
-            let cap = part.chars().next().unwrap(); 
-            let interior = &part[1..part.len()-1];
-            let values: Vec<usize> = interior.split(',').map(|num| num.parse::<usize>().unwrap()).collect();
-            match cap {
-                '(' => {
-                    let mut button = 0_u16;
-                    for index in values {
-                        button.set_bit(index, true);
-                    }
-                    buttons.push(button);
-                },
-                '{' => joltage = values,
-                _   => panic!("Unexpected cap."),
+        let mut pos = lights.len() + 2;
+        for capsule in capsules {
+            let start = line[pos..].find(capsule.as_str()).unwrap() + pos;
+            pos = start + capsule.len();
+
+            let (indices,) = parse_line!(&capsule, "({})" => Vec<usize>)
+                .map_err(|err| fail(err.within(line, start)))?;
+            let mut button = 0_u16;
+            for index in indices {
+                if index >= lights.len() {
+                    let expected = format!("a light index below {}", lights.len());
+                    return Err(fail(ParseError::new(line, start + 2, expected, index.to_string())));
+                }
+                button.set_bit(index, true);
             }
+            buttons.push(button);
         }
 
         machines.push(Machine { target, buttons, joltage });
     }
 
-    machines
+    Ok(machines)
 }
 
 #[aoc(day10, part1)]
@@ -127,25 +132,46 @@ pub fn solve_part1(input: &Vec<Machine>) -> usize {
 }
 
 #[aoc(day10, part2)]
-pub fn solve_part2(input: &Vec<Machine>) -> usize {
-    let pb = ProgressBar::new(input.len() as u64);
-    pb.set_style(ProgressStyle::default_bar()
-        .template("[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} ({eta})").unwrap());
+pub fn solve_part2(input: &[Machine]) -> usize {
+    solve_part2_with(input, &Context::default())
+}
+
+pub fn solve_part2_with(input: &[Machine], ctx: &Context) -> usize {
+    let progress = ctx.progress();
+    progress.start("machines", input.len() as u64);
 
-    let total: usize = input
+    let total = input
         .par_iter()
-        .map(|machine| {
-            let result = machine.fewest_presses_joltage();
-            pb.inc(1);
-            result
-        })
+        .map(|machine| machine.fewest_presses_joltage())
+        .report(progress)
         .sum();
 
-    pb.finish();
+    progress.finish();
 
     total
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = Vec<Machine>;
+    type Params = ();
+
+    fn parse(input: &str, _params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input, _ctx: &Context) -> Answer {
+        solve_part1(input).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, ctx| solve_part2_with(input, ctx).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,11 +182,11 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 7);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 7);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST)), 33);
+        assert_eq!(solve_part2(&input_generator(TEST).unwrap()), 33);
     }
 }
\ No newline at end of file
@@ -5,30 +5,34 @@ type Dict = HashMap<String, usize>;
 
 // Using nodes as numeric IDs speeds up processing... a lot.
 #[aoc_generator(day11)]
-pub fn input_generator(input: &str) -> (Devices, Dict) {
-    let mut dict = HashMap::new();
+pub fn input_generator(input: &str) -> Result<(Devices, Dict), ParseError> {
+    let lines = parse_lines!(input, "{}: {}" => String, Vec<String>)
+        .map_err(|err| err.on_day(11))?;
 
-    input
-        .lines()
+    let mut dict: Dict = lines
+        .iter()
         .enumerate()
-        .filter_map(|(idx, line)| {
-            Some((line.get(0..3)?, idx))
-        })
-        .for_each(|(key, idx)| {
-            dict.insert(key.to_string(), idx);
-        });
-
+        .map(|(idx, (node, _))| (node.clone(), idx))
+        .collect();
     dict.insert("out".to_string(), dict.len());
 
-    let devices = input
-        .lines()
-        .map(|line| {
-            let (node, out) = line.split_once(": ").unwrap();
-            (*dict.get(node).unwrap(), out.split_whitespace().map(|node| *dict.get(node).unwrap()).collect())
-        })
-        .collect();
+    let mut devices = HashMap::new();
+    for ((node, out), (row, line)) in lines.iter().zip(input.lines().enumerate()) {
+        let mut outputs = Vec::new();
+        for name in out {
+            match dict.get(name) {
+                Some(&id) => outputs.push(id),
+                None => {
+                    let line = line.trim_end();
+                    let column = line.rfind(name.as_str()).unwrap() + 1;
+                    return Err(ParseError::new(line, column, "a listed device or `out`", name.as_str()).at_line(row + 1).on_day(11));
+                },
+            }
+        }
+        devices.insert(dict[node], outputs);
+    }
 
-    (devices, dict)
+    Ok((devices, dict))
 }
 
 fn count_paths(current: &usize, target: &usize, devices: &Devices, cache: &mut Vec<usize>) -> usize {
@@ -38,13 +42,10 @@ fn count_paths(current: &usize, target: &usize, devices: &Devices, cache: &mut V
     }
 
     let mut result = 0;
-    // It remains a mystery why the node check isn't returning 1 when it's
-    // supposed to, and continues to pass a non-existent node to the next
-    // iteration.
     if let Some(node_list) = devices.get(current) {
         for &node in node_list {
             if node == *target {
-                return 1;
+                result += 1;
             } else {
                 result += count_paths(&node, target, devices, cache);
             }
@@ -65,9 +66,9 @@ pub fn solve_part1((devices, dict): &(Devices, Dict)) -> usize {
 }
 
 // Rather than find all paths between 'svr' and 'out', and checking if 'dac' and
-// 'fft' are in those paths, I chose to check 'svr' to 'dac' and 'svr' to 'fft'
-// to see which is closer. As it turns out, the sequence is svr-fft-dac-out. Find
-// all paths between each stage and multiply them.
+// 'fft' are in those paths, find all paths between each stage and multiply
+// them. My input goes svr-fft-dac-out, but either order is possible, and with
+// no cycles at most one of them has any paths.
 #[aoc(day11, part2)]
 pub fn solve_part2((devices, dict): &(Devices, Dict)) -> usize {
     let svr = dict.get("svr").unwrap();
@@ -75,11 +76,30 @@ pub fn solve_part2((devices, dict): &(Devices, Dict)) -> usize {
     let dac = dict.get("dac").unwrap();
     let out = dict.get("out").unwrap();
 
-    let svr_fft = count_paths(svr, fft, &devices, &mut vec![usize::MAX; dict.len()]);
-    let fft_dac = count_paths(fft, dac, &devices, &mut vec![usize::MAX; dict.len()]);
-    let dac_out = count_paths(dac, out, &devices, &mut vec![usize::MAX; dict.len()]);
+    let paths = |from, to| count_paths(from, to, devices, &mut vec![usize::MAX; dict.len()]);
+
+    paths(svr, fft) * paths(fft, dac) * paths(dac, out) + paths(svr, dac) * paths(dac, fft) * paths(fft, out)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input = (Devices, Dict);
+    type Params = ();
 
-    svr_fft * fft_dac * dac_out
+    fn parse(input: &str, _params: &Self::Params) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input, _ctx: &Context) -> Answer {
+        solve_part1(input).into()
+    }
+
+    fn part2() -> Option<DaySolver<Self>> {
+        Some(|input, _ctx| solve_part2(input).into())
+    }
 }
 
 #[cfg(test)]
@@ -113,11 +133,21 @@ hhh: out";
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST1)), 5);
+        assert_eq!(solve_part1(&input_generator(TEST1).unwrap()), 5);
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(solve_part2(&input_generator(TEST2)), 2);
+        assert_eq!(solve_part2(&input_generator(TEST2).unwrap()), 2);
+    }
+
+    #[test]
+    fn through_target_test() {
+        assert_eq!(solve_part1(&input_generator("you: dac out\ndac: fft out\nfft: out").unwrap()), 3);
+    }
+
+    #[test]
+    fn dac_first_test() {
+        assert_eq!(solve_part2(&input_generator("svr: dac\ndac: fft\nfft: out").unwrap()), 1);
     }
 }
\ No newline at end of file
@@ -1,52 +1,77 @@
+use crate::prelude::*;
+
+#[derive(Debug)]
 pub struct Region {
-    size: (usize, usize),
-    quants: [usize; 6],
+    area: Rect,
+    quants: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Present shapes listed before the regions.
+    pub shapes: usize,
 }
 
+impl Default for Params {
+    fn default() -> Self {
+        Self { shapes: 6 }
+    }
+}
+
+params!(Params { shapes });
+
 #[aoc_generator(day12)]
-pub fn input_generator(input: &str) -> (Vec<usize>, Vec<Region>) {
-    let mut parts = input.split("\n\n");
-
-    (
-        (0..6)
-            .map(|_| {
-                parts
-                    .next()
-                    .unwrap()
-                    .lines()
-                    .skip(1)
-                    .flat_map(|line| line.chars())
-                    .filter(|&ch| ch == '#')
-                    .count()
-            })
-            .collect(),
-        parts
-            .next()
-            .unwrap()
-            .lines()
-            .map(|line| {
-                let (first, second) = line.split_once(": ").unwrap();
-                
-                let size: (usize, usize) = match first.split_once('x') {
-                    Some((width_str, height_str)) => (
-                        width_str.parse().unwrap_or_default(),
-                        height_str.parse().unwrap_or_default()
-                    ),
-                    None => unreachable!(),
-                };
-
-                let mut quants = [0; 6];
-                second
-                    .split_whitespace()
-                    .zip(&mut quants)
-                    .for_each(|(count, idx)| {
-                        *idx = count.parse::<usize>().unwrap();
-                    });
-
-                Region { size, quants }
-            })
-            .collect()
-    )
+pub fn input_generator(input: &str) -> Result<(Vec<usize>, Vec<Region>), ParseError> {
+    input_generator_with(input, &Params::default())
+}
+
+pub fn input_generator_with(input: &str, params: &Params) -> Result<(Vec<usize>, Vec<Region>), ParseError> {
+    let parts = sections(input);
+    if parts.len() != params.shapes + 1 {
+        return Err(ParseError::missing(format!("{} shapes followed by the regions", params.shapes)).on_day(12));
+    }
+
+    let mut shapes = Vec::new();
+    for &(start, shape) in &parts[..params.shapes] {
+        let mut cells = 0;
+        for (row, line) in shape.lines().enumerate() {
+            let line = line.trim_end();
+            let fail = |err: ParseError| err.at_line(start + row + 1).on_day(12);
+            if row == 0 {
+                parse_line!(line, "{}:" => usize).map_err(fail)?;
+                continue;
+            }
+            for (col, ch) in line.chars().enumerate() {
+                match ch {
+                    '#' => cells += 1,
+                    '.' => {},
+                    _   => return Err(fail(ParseError::new(line, col + 1, "`#` or `.`", ch))),
+                }
+            }
+        }
+        shapes.push(cells);
+    }
+
+    let (start, regions) = parts[params.shapes];
+    let regions = parse_lines!(regions, "{}x{}: {}" => i64, i64, Vec<usize>)
+        .map_err(|err| err.shift_lines(start).on_day(12))?
+        .into_iter()
+        .zip(regions.lines().enumerate())
+        .map(|((width, height, counts), (row, line))| {
+            if counts.len() != params.shapes {
+                let line = line.trim_end();
+                let column = line.find(": ").unwrap() + 3;
+                let expected = format!("{} shape counts", params.shapes);
+                return Err(ParseError::new(line, column, expected, format!("{} counts", counts.len()))
+                    .at_line(start + row + 1)
+                    .on_day(12));
+            }
+
+            Ok(Region { area: Rect::exclusive((0, 0), (width, height)), quants: counts })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((shapes, regions))
 }
 
 #[aoc(day12, part1)]
@@ -54,9 +79,9 @@ pub fn solve_part1((shapes, regions): &(Vec<usize>, Vec<Region>)) -> usize {
     let mut sum = 0;
 
     for region in regions {
-        if (region.size.0 / 3) * (region.size.1 / 3) >= region.quants.iter().sum() {
+        if (region.area.width() as usize / 3) * (region.area.height() as usize / 3) >= region.quants.iter().sum() {
             let required_area = region.quants.iter().enumerate().map(|(idx, &count)| count * shapes[idx]).sum::<usize>();
-            if required_area <= region.size.0 * region.size.1 {
+            if required_area <= region.area.area() as usize {
                 sum += 1;
             }
         }
@@ -65,6 +90,23 @@ pub fn solve_part1((shapes, regions): &(Vec<usize>, Vec<Region>)) -> usize {
     sum
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = (Vec<usize>, Vec<Region>);
+    type Params = Params;
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        input_generator_with(input, params)
+    }
+
+    fn part1(input: &Self::Input, _ctx: &Context<Params>) -> Answer {
+        solve_part1(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,6 +147,21 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        assert_eq!(solve_part1(&input_generator(TEST)), 2);
+        assert_eq!(solve_part1(&input_generator(TEST).unwrap()), 2);
+    }
+
+    #[test]
+    fn error_test() {
+        let err = input_generator(&TEST.replace("12x5: 1 0 1 0 3 2", "12x5: 1 0 1 0 3")).unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (12, 33, 7));
+        assert_eq!(err.found, "5 counts");
+    }
+
+    #[test]
+    fn shapes_test() {
+        let shapes = TEST.split("\n\n").take(5).collect::<Vec<_>>().join("\n\n");
+        let input = format!("{shapes}\n\n4x4: 0 0 0 0 2\n12x5: 1 0 1 0 2\n12x5: 1 0 1 0 3");
+        assert!(input_generator(&input).is_err());
+        assert_eq!(input_generator_with(&input, &Params { shapes: 5 }).unwrap().1.len(), 3);
     }
 }
\ No newline at end of file
@@ -0,0 +1,32 @@
+use super::*;
+
+/// Dial rotations for day 1, one `L`/`R` and a step count per line.
+pub struct Rotations {
+    pub rotations: usize,
+    /// Largest step count. Anything over 99 goes round the dial more than once.
+    pub max_steps: usize,
+}
+
+impl Default for Rotations {
+    fn default() -> Self {
+        Self { rotations: 4000, max_steps: 999 }
+    }
+}
+
+impl Generator for Rotations {
+    const DAY: u32 = 1;
+
+    fn with_size(size: usize) -> Self {
+        Self { rotations: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        (0..self.rotations)
+            .map(|_| {
+                let dir = if rng.random_bool(0.5) { 'L' } else { 'R' };
+                format!("{dir}{}", rng.random_range(1..=self.max_steps))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
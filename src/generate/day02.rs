@@ -0,0 +1,37 @@
+use super::*;
+
+/// Product ID ranges for day 2, all on one comma separated line.
+pub struct IdRanges {
+    pub ranges: usize,
+    /// Most digits in any ID.
+    pub max_digits: u32,
+    /// Most IDs in a single range. The solvers walk every ID, so this is the
+    /// knob that matters for run time.
+    pub max_span: usize,
+}
+
+impl Default for IdRanges {
+    fn default() -> Self {
+        Self { ranges: 35, max_digits: 10, max_span: 100_000 }
+    }
+}
+
+impl Generator for IdRanges {
+    const DAY: u32 = 2;
+
+    fn with_size(size: usize) -> Self {
+        Self { ranges: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        (0..self.ranges)
+            .map(|_| {
+                let digits = rng.random_range(1..=self.max_digits);
+                let start = rng.random_range(10_usize.pow(digits - 1)..10_usize.pow(digits));
+                let end = start + rng.random_range(0..self.max_span);
+                format!("{start}-{end}")
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
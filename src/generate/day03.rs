@@ -0,0 +1,33 @@
+use super::*;
+
+/// Battery banks for day 3, one row of joltage digits per bank.
+pub struct BatteryBanks {
+    pub banks: usize,
+    /// Batteries per bank. Part 2 picks twelve, so keep this above that.
+    pub batteries: usize,
+}
+
+impl Default for BatteryBanks {
+    fn default() -> Self {
+        Self { banks: 200, batteries: 100 }
+    }
+}
+
+impl Generator for BatteryBanks {
+    const DAY: u32 = 3;
+
+    fn with_size(size: usize) -> Self {
+        Self { banks: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        (0..self.banks)
+            .map(|_| {
+                (0..self.batteries)
+                    .map(|_| char::from(b'0' + rng.random_range(1..=9)))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+/// Paper roll grid for day 4.
+pub struct PaperGrid {
+    pub width: usize,
+    pub height: usize,
+    /// Chance of any one cell holding a roll of paper.
+    pub density: f64,
+}
+
+impl Default for PaperGrid {
+    fn default() -> Self {
+        Self { width: 140, height: 140, density: 0.7 }
+    }
+}
+
+impl Generator for PaperGrid {
+    const DAY: u32 = 4;
+
+    /// A square grid with `size` rows.
+    fn with_size(size: usize) -> Self {
+        Self { width: size, height: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        (0..self.height)
+            .map(|_| {
+                (0..self.width)
+                    .map(|_| if rng.random_bool(self.density) { '@' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
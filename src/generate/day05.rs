@@ -0,0 +1,37 @@
+use super::*;
+
+/// Fresh ingredient ranges and available IDs for day 5.
+pub struct Inventory {
+    pub ranges: usize,
+    pub ids: usize,
+    /// IDs are drawn from `1..=max_id`.
+    pub max_id: usize,
+    /// Most IDs in a single fresh range.
+    pub max_span: usize,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self { ranges: 180, ids: 1000, max_id: 500_000_000_000_000, max_span: 20_000_000_000_000 }
+    }
+}
+
+impl Generator for Inventory {
+    const DAY: u32 = 5;
+
+    fn with_size(size: usize) -> Self {
+        Self { ranges: size, ids: size * 5, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        let ranges = (0..self.ranges).map(|_| {
+            let start = rng.random_range(1..=self.max_id);
+            let end = min(self.max_id, start + rng.random_range(0..self.max_span));
+            format!("{start}-{end}")
+        });
+        let ranges: Vec<String> = ranges.collect();
+        let ids: Vec<String> = (0..self.ids).map(|_| rng.random_range(1..=self.max_id).to_string()).collect();
+
+        format!("{}\n\n{}", ranges.join("\n"), ids.join("\n"))
+    }
+}
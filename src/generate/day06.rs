@@ -0,0 +1,55 @@
+use super::*;
+
+/// Cephalopod maths worksheet for day 6. Each problem is a column of numbers
+/// with its operator underneath, and problems are separated by a column of
+/// spaces.
+pub struct Worksheet {
+    pub problems: usize,
+    /// Numbers in each problem.
+    pub rows: usize,
+    pub max_digits: u32,
+}
+
+impl Default for Worksheet {
+    fn default() -> Self {
+        Self { problems: 1000, rows: 4, max_digits: 4 }
+    }
+}
+
+impl Generator for Worksheet {
+    const DAY: u32 = 6;
+
+    fn with_size(size: usize) -> Self {
+        Self { problems: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        let mut lines = vec![String::new(); self.rows + 1];
+
+        for problem in 0..self.problems {
+            let numbers: Vec<String> = (0..self.rows)
+                .map(|_| {
+                    let digits = rng.random_range(1..=self.max_digits);
+                    rng.random_range(10_usize.pow(digits - 1)..10_usize.pow(digits)).to_string()
+                })
+                .collect();
+            let width = numbers.iter().map(String::len).max().unwrap();
+            let oper = if rng.random_bool(0.5) { '+' } else { '*' };
+            let left = rng.random_bool(0.5);
+
+            if problem > 0 {
+                lines.iter_mut().for_each(|line| line.push(' '));
+            }
+            for (line, number) in lines.iter_mut().zip(&numbers) {
+                if left {
+                    line.push_str(&format!("{number:<width$}"));
+                } else {
+                    line.push_str(&format!("{number:>width$}"));
+                }
+            }
+            lines[self.rows].push_str(&format!("{oper:<width$}"));
+        }
+
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+/// Tachyon manifold for day 7: a start on the first line and rows of
+/// splitters below it, with an empty line between each row.
+///
+/// Splitters only go where a beam could reach, alternating in parity from
+/// row to row as in the puzzle, so two are never side by side.
+pub struct Manifold {
+    pub width: usize,
+    /// Rows of splitters, not counting the start.
+    pub rows: usize,
+    /// Chance of each reachable cell holding a splitter.
+    pub density: f64,
+}
+
+impl Default for Manifold {
+    fn default() -> Self {
+        Self { width: 141, rows: 70, density: 0.8 }
+    }
+}
+
+impl Generator for Manifold {
+    const DAY: u32 = 7;
+
+    /// `size` rows of splitters, with room for all of them to spread out.
+    fn with_size(size: usize) -> Self {
+        Self { width: 2 * size + 1, rows: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        let start = self.width / 2;
+        let blank = ".".repeat(self.width);
+        let mut lines = vec![format!("{}S{}", &blank[..start], &blank[start + 1..]), blank.clone()];
+
+        for row in 0..self.rows {
+            let line: String = (0..self.width)
+                .map(|col| {
+                    let offset = col.abs_diff(start);
+                    // Keep clear of the edges so a split never leaves the manifold.
+                    let reachable = offset <= row && offset % 2 == row % 2 && col > 0 && col < self.width - 1;
+                    if reachable && rng.random_bool(self.density) { '^' } else { '.' }
+                })
+                .collect();
+            lines.push(line);
+            lines.push(blank.clone());
+        }
+
+        lines.join("\n")
+    }
+}
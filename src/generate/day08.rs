@@ -0,0 +1,44 @@
+use super::*;
+
+/// Junction box positions in 3D for day 8.
+pub struct JunctionBoxes {
+    pub boxes: usize,
+    /// Coordinates are drawn from `0..=max_coord`.
+    pub max_coord: i64,
+}
+
+impl Default for JunctionBoxes {
+    fn default() -> Self {
+        Self { boxes: 1000, max_coord: 99_999 }
+    }
+}
+
+impl Generator for JunctionBoxes {
+    const DAY: u32 = 8;
+
+    fn with_size(size: usize) -> Self {
+        Self { boxes: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        let mut boxes = HashSet::new();
+        while boxes.len() < self.boxes {
+            boxes.insert((
+                rng.random_range(0..=self.max_coord),
+                rng.random_range(0..=self.max_coord),
+                rng.random_range(0..=self.max_coord),
+            ));
+        }
+
+        // Sorted first so the output doesn't depend on HashSet ordering.
+        let mut boxes: Vec<_> = boxes.into_iter().collect();
+        boxes.sort_unstable();
+        boxes.shuffle(rng);
+
+        boxes
+            .iter()
+            .map(|(x, y, z)| format!("{x},{y},{z}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
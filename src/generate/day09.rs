@@ -0,0 +1,88 @@
+use super::*;
+
+/// Red tile loop for day 9: the corners of a rectilinear polygon, each in
+/// line with the one before.
+///
+/// The polygon is a row of vertical bars whose neighbours always overlap, so
+/// it is connected, has no holes and never touches itself. Both the top and
+/// the bottom edge are jagged, which gives plenty of concave notches.
+pub struct TileLoop {
+    /// Vertical bars making up the polygon.
+    pub columns: usize,
+    /// Coordinates are drawn from `0..=max_coord`.
+    pub max_coord: usize,
+    /// Largest step, in grid lines, between neighbouring bar ends.
+    pub max_step: usize,
+    /// Smallest distance between two different X or Y coordinates. At 1,
+    /// notches can be too narrow to hold a tile.
+    pub min_gap: usize,
+}
+
+impl Default for TileLoop {
+    fn default() -> Self {
+        Self { columns: 125, max_coord: 99_999, max_step: 6, min_gap: 1 }
+    }
+}
+
+/// `count` sorted values from `0..=max`, at least `gap` apart.
+fn grid_lines(rng: &mut StdRng, count: usize, max: usize, gap: usize) -> Vec<usize> {
+    let spare = max + 1 - (count - 1) * (gap - 1);
+    let mut lines = rand::seq::index::sample(rng, spare, count).into_vec();
+    lines.sort_unstable();
+    lines.iter().enumerate().map(|(idx, line)| line + idx * (gap - 1)).collect()
+}
+
+impl Generator for TileLoop {
+    const DAY: u32 = 9;
+
+    fn with_size(size: usize) -> Self {
+        Self { columns: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        let rows = 4 * self.columns + 4;
+        let gap = max(self.min_gap, 1);
+        let xs = grid_lines(rng, self.columns + 1, self.max_coord, gap);
+        let ys = grid_lines(rng, rows, self.max_coord, gap);
+
+        // Each bar spans grid lines lo..hi, overlapping the previous bar.
+        let mut bars: Vec<(usize, usize)> = vec![(rows / 3, 2 * rows / 3)];
+        while bars.len() < self.columns {
+            let (lo, hi) = *bars.last().unwrap();
+            let step = |rng: &mut StdRng, value: usize| {
+                (value + rng.random_range(0..=2 * self.max_step)).saturating_sub(self.max_step).clamp(0, rows - 1)
+            };
+            let bar = (step(rng, lo), step(rng, hi));
+            if bar.0 < bar.1 && max(lo, bar.0) < min(hi, bar.1) {
+                bars.push(bar);
+            }
+        }
+
+        // Along the top from left to right, then back along the bottom.
+        let mut corners = Vec::new();
+        for (col, &(_, hi)) in bars.iter().enumerate() {
+            corners.push((xs[col], ys[hi]));
+            corners.push((xs[col + 1], ys[hi]));
+        }
+        for (col, &(lo, _)) in bars.iter().enumerate().rev() {
+            corners.push((xs[col + 1], ys[lo]));
+            corners.push((xs[col], ys[lo]));
+        }
+
+        // Drop the corners where two bars of the same height meet.
+        let len = corners.len();
+        let corners: Vec<(usize, usize)> = (0..len)
+            .filter(|&idx| {
+                let (prev, here, next) = (corners[(idx + len - 1) % len], corners[idx], corners[(idx + 1) % len]);
+                !(prev.0 == here.0 && here.0 == next.0 || prev.1 == here.1 && here.1 == next.1)
+            })
+            .map(|idx| corners[idx])
+            .collect();
+
+        corners
+            .iter()
+            .map(|(x, y)| format!("{x},{y}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
\ No newline at end of file
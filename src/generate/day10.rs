@@ -0,0 +1,74 @@
+use super::*;
+
+/// Factory machines for day 10: indicator lights, buttons and joltage
+/// requirements.
+///
+/// The lights and joltages come from pressing the buttons, so every machine
+/// has a solution for both parts.
+pub struct Machines {
+    pub machines: usize,
+    /// Lights per machine are drawn from `3..=max_lights`, at most 16.
+    pub max_lights: usize,
+    /// Most presses of any one button when making up the joltages.
+    pub max_presses: usize,
+}
+
+impl Default for Machines {
+    fn default() -> Self {
+        Self { machines: 180, max_lights: 10, max_presses: 20 }
+    }
+}
+
+impl Generator for Machines {
+    const DAY: u32 = 10;
+
+    fn with_size(size: usize) -> Self {
+        Self { machines: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        (0..self.machines)
+            .map(|_| {
+                let lights = rng.random_range(3..=min(self.max_lights, 16));
+                let mut buttons: Vec<Vec<usize>> = (0..rng.random_range(lights - 1..=lights + 3))
+                    .map(|_| {
+                        let count = rng.random_range(1..lights);
+                        let mut wired = rand::seq::index::sample(rng, lights, count).into_vec();
+                        wired.sort_unstable();
+                        wired
+                    })
+                    .collect();
+
+                // Every light needs a button, or its joltage can only be zero.
+                for light in 0..lights {
+                    if !buttons.iter().any(|button| button.contains(&light)) {
+                        buttons.push(vec![light]);
+                    }
+                }
+
+                let mut state = vec![false; lights];
+                let mut joltage = vec![0; lights];
+                while !state.contains(&true) {
+                    state.fill(false);
+                    for button in buttons.iter().filter(|_| rng.random_bool(0.5)) {
+                        button.iter().for_each(|&light| state[light] = !state[light]);
+                    }
+                }
+                for button in &buttons {
+                    let presses = rng.random_range(0..=self.max_presses);
+                    button.iter().for_each(|&light| joltage[light] += presses);
+                }
+
+                let state: String = state.iter().map(|&on| if on { '#' } else { '.' }).collect();
+                let buttons: Vec<String> = buttons
+                    .iter()
+                    .map(|button| format!("({})", button.iter().map(usize::to_string).collect::<Vec<_>>().join(",")))
+                    .collect();
+                let joltage: Vec<String> = joltage.iter().map(usize::to_string).collect();
+
+                format!("[{state}] {} {{{}}}", buttons.join(" "), joltage.join(","))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,92 @@
+use super::*;
+
+/// Device graph for day 11. Devices sit in layers and only connect to later
+/// layers, with anything past the last layer going to `out`, so there are no
+/// cycles and the number of paths stays within `max_outputs ^ layers`.
+///
+/// `svr` and `you` are in the first layer, with `fft` and `dac` further down
+/// in either order. A chain of connections makes sure that both of them lie
+/// on a path from `svr` to `out`, and that `you` reaches `out`.
+pub struct DeviceGraph {
+    pub layers: usize,
+    /// Devices per layer.
+    pub width: usize,
+    /// Most outputs from any one device.
+    pub max_outputs: usize,
+    /// How many layers ahead a device can connect to.
+    pub reach: usize,
+}
+
+impl Default for DeviceGraph {
+    fn default() -> Self {
+        Self { layers: 24, width: 24, max_outputs: 3, reach: 2 }
+    }
+}
+
+const RESERVED: [&str; 5] = ["svr", "you", "fft", "dac", "out"];
+
+impl Generator for DeviceGraph {
+    const DAY: u32 = 11;
+
+    fn with_size(size: usize) -> Self {
+        Self { layers: max(size, 3), ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        let (layers, width) = (max(self.layers, 3), max(self.width, 2));
+        let mut names = HashSet::new();
+        let mut devices: Vec<Vec<String>> = (0..layers)
+            .map(|_| {
+                (0..width)
+                    .map(|_| loop {
+                        let name: String = (0..3).map(|_| char::from(rng.random_range(b'a'..=b'z'))).collect();
+                        if !RESERVED.contains(&name.as_str()) && names.insert(name.clone()) {
+                            break name;
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        devices[0][0] = "svr".to_string();
+        devices[0][1] = "you".to_string();
+        let first = rng.random_range(1..layers - 1);
+        let second = rng.random_range(first + 1..layers);
+        let (first_name, second_name) = if rng.random_bool(0.5) { ("fft", "dac") } else { ("dac", "fft") };
+        devices[first][0] = first_name.to_string();
+        devices[second][0] = second_name.to_string();
+
+        // Outputs as (layer, index), with `layers` itself standing for `out`.
+        let out = (layers, 0);
+        let mut outputs: Vec<Vec<HashSet<(usize, usize)>>> = vec![vec![HashSet::new(); width]; layers];
+        for (layer, row) in outputs.iter_mut().enumerate() {
+            for device in row.iter_mut() {
+                for _ in 0..rng.random_range(1..=self.max_outputs) {
+                    let target = layer + rng.random_range(1..=max(self.reach, 1));
+                    device.insert(if target >= layers { out } else { (target, rng.random_range(0..width)) });
+                }
+            }
+        }
+
+        // The guaranteed chains, through whichever device is at the front of
+        // each layer. `you` joins the chain in the second layer.
+        for (layer, row) in outputs.iter_mut().enumerate() {
+            row[0].insert(if layer + 1 == layers { out } else { (layer + 1, 0) });
+        }
+        outputs[0][1].insert((1, 0));
+
+        let name = |(layer, idx): (usize, usize)| if layer == layers { "out" } else { devices[layer][idx].as_str() };
+        let mut lines = Vec::new();
+        for (layer, row) in outputs.iter().enumerate() {
+            for (idx, targets) in row.iter().enumerate() {
+                let mut targets: Vec<(usize, usize)> = targets.iter().copied().collect();
+                targets.sort_unstable();
+                let targets: Vec<&str> = targets.into_iter().map(name).collect();
+                lines.push(format!("{}: {}", name((layer, idx)), targets.join(" ")));
+            }
+        }
+        lines.shuffle(rng);
+
+        lines.join("\n")
+    }
+}
\ No newline at end of file
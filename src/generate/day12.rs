@@ -0,0 +1,64 @@
+use super::*;
+
+/// Present shapes and tree regions for day 12: 3x3 shapes followed by
+/// regions listing how many of each shape must fit.
+pub struct Regions {
+    pub regions: usize,
+    /// Six in the real puzzle, see `day12::Params`.
+    pub shapes: usize,
+    /// Region sides are drawn from `min_side..=max_side`.
+    pub min_side: usize,
+    pub max_side: usize,
+    /// Presents asked for, as a fraction of the 3x3 slots in the region.
+    /// Around 1.0 gives a mix of regions that do and don't fit.
+    pub fill: f64,
+}
+
+impl Default for Regions {
+    fn default() -> Self {
+        Self { regions: 1000, shapes: 6, min_side: 35, max_side: 50, fill: 1.0 }
+    }
+}
+
+impl Generator for Regions {
+    const DAY: u32 = 12;
+
+    fn with_size(size: usize) -> Self {
+        Self { regions: size, ..Self::default() }
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        let mut blocks = Vec::new();
+
+        for idx in 0..self.shapes {
+            // Always the middle cell, and five to seven cells in all.
+            let count = rng.random_range(4..=6);
+            let mut cells = rand::seq::index::sample(rng, 8, count).into_vec();
+            cells.iter_mut().filter(|cell| **cell >= 4).for_each(|cell| *cell += 1);
+            cells.push(4);
+
+            let shape: Vec<String> = (0..3)
+                .map(|row| (0..3).map(|col| if cells.contains(&(row * 3 + col)) { '#' } else { '.' }).collect())
+                .collect();
+            blocks.push(format!("{idx}:\n{}", shape.join("\n")));
+        }
+
+        let regions: Vec<String> = (0..self.regions)
+            .map(|_| {
+                let width = rng.random_range(self.min_side..=self.max_side);
+                let height = rng.random_range(self.min_side..=self.max_side);
+                let presents = (((width / 3) * (height / 3)) as f64 * self.fill * rng.random_range(0.8..1.2)) as usize;
+
+                let mut counts = vec![0; self.shapes];
+                for _ in 0..presents {
+                    counts[rng.random_range(0..self.shapes)] += 1;
+                }
+                let counts: Vec<String> = counts.iter().map(usize::to_string).collect();
+                format!("{width}x{height}: {}", counts.join(" "))
+            })
+            .collect();
+        blocks.push(regions.join("\n"));
+
+        blocks.join("\n\n")
+    }
+}
\ No newline at end of file
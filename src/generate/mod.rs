@@ -0,0 +1,113 @@
+// Random puzzle inputs for stress testing, benchmarks and checking solutions
+// against each other. Every generator takes the RNG it draws from, so the
+// same seed and settings always give the same input.
+//
+// (Not called `gen`, which is a reserved word as of the 2024 edition.)
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+
+pub use day01::*;
+pub use day02::*;
+pub use day03::*;
+pub use day04::*;
+pub use day05::*;
+pub use day06::*;
+pub use day07::*;
+pub use day08::*;
+pub use day09::*;
+pub use day10::*;
+pub use day11::*;
+pub use day12::*;
+
+pub use rand::{Rng, SeedableRng, rngs::StdRng};
+use crate::prelude::*;
+use rand::seq::SliceRandom;
+
+/// A configurable generator for one day's input. `Default` gives roughly
+/// the size of a real input.
+pub trait Generator: Default {
+    const DAY: u32;
+
+    /// Same settings but scaled to `size` of the main thing being counted,
+    /// e.g. rotations for day 1 or rows for day 4.
+    fn with_size(size: usize) -> Self;
+
+    /// Text in the same format as the puzzle input.
+    fn generate(&self, rng: &mut StdRng) -> String;
+
+    fn generate_seeded(&self, seed: u64) -> String {
+        self.generate(&mut StdRng::seed_from_u64(seed))
+    }
+}
+
+/// Default-sized input for a day, or one scaled to `size` if given.
+pub fn random_input(day: u32, size: Option<usize>, seed: u64) -> Option<String> {
+    fn make<G: Generator>(size: Option<usize>, seed: u64) -> String {
+        size.map_or_else(G::default, G::with_size).generate_seeded(seed)
+    }
+
+    Some(match day {
+        1  => make::<Rotations>(size, seed),
+        2  => make::<IdRanges>(size, seed),
+        3  => make::<BatteryBanks>(size, seed),
+        4  => make::<PaperGrid>(size, seed),
+        5  => make::<Inventory>(size, seed),
+        6  => make::<Worksheet>(size, seed),
+        7  => make::<Manifold>(size, seed),
+        8  => make::<JunctionBoxes>(size, seed),
+        9  => make::<TileLoop>(size, seed),
+        10 => make::<Machines>(size, seed),
+        11 => make::<DeviceGraph>(size, seed),
+        12 => make::<Regions>(size, seed),
+        _  => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Solution;
+
+    fn parses<S: Solution, G: Generator>(size: usize) {
+        for seed in 0..5 {
+            let input = G::with_size(size).generate_seeded(seed);
+            if let Err(err) = S::parse(&input, &Default::default()) {
+                panic!("day {} seed {seed}: {err}", S::DAY);
+            }
+        }
+        assert_eq!(S::DAY, G::DAY);
+    }
+
+    #[test]
+    fn generators_test() {
+        parses::<crate::day01::Day01, Rotations>(50);
+        parses::<crate::day02::Day02, IdRanges>(10);
+        parses::<crate::day03::Day03, BatteryBanks>(10);
+        parses::<crate::day04::Day04, PaperGrid>(12);
+        parses::<crate::day05::Day05, Inventory>(10);
+        parses::<crate::day06::Day06, Worksheet>(10);
+        parses::<crate::day07::Day07, Manifold>(12);
+        parses::<crate::day08::Day08, JunctionBoxes>(20);
+        parses::<crate::day09::Day09, TileLoop>(8);
+        parses::<crate::day10::Day10, Machines>(5);
+        parses::<crate::day11::Day11, DeviceGraph>(6);
+        parses::<crate::day12::Day12, Regions>(10);
+    }
+
+    #[test]
+    fn seeded_test() {
+        assert_eq!(random_input(9, None, 7), random_input(9, None, 7));
+        assert_ne!(random_input(9, None, 7), random_input(9, None, 8));
+        assert_eq!(random_input(13, None, 7), None);
+    }
+}
\ No newline at end of file
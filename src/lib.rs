@@ -16,10 +16,20 @@ pub mod day12;
 
 aoc_lib! { year = 2025 }
 
+pub mod generate;
+pub mod memory;
+pub mod params;
+pub mod progress;
+pub mod reference;
+pub mod regression;
+pub mod runner;
+pub mod solution;
 mod utils;
 
 pub mod prelude {
-    pub use crate::{utils::*, hashset};
+    pub use crate::progress::ParallelProgress;
+    pub use crate::solution::{Answer, Context, DaySolver, Solution, Solver};
+    pub use crate::{utils::*, btreemap, counter, hashmap, hashset, params, parse_line, parse_lines};
     pub use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
     pub use std::cmp::{Ordering, min, max};
     pub use std::fmt;
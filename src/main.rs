@@ -1,9 +1,338 @@
 extern crate aoc_2025;
-extern crate aoc_runner;
-extern crate aoc_runner_derive;
 
-use aoc_runner_derive::aoc_main;
+use aoc_2025::memory::{AllocStats, Bytes};
+use aoc_2025::params::parse_setting;
+use aoc_2025::progress::{Bar, Log};
+use aoc_2025::runner::{Entry, Outcome, input_path, registry};
+use aoc_2025::solution::Context;
+use std::collections::HashMap;
+use std::io::{IsTerminal, Read};
+use std::process::ExitCode;
 
-aoc_main! { lib = aoc_2025 }
+const USAGE: &str = "\
+Usage: aoc_2025 [OPTIONS] [DAY...]
 
-// Check https://github.com/gobanos/cargo-aoc
\ No newline at end of file
+Runs the solutions for the given days, printing each answer along with the
+time spent in the generator and in the solver. Inputs are read from
+input/2025/dayN.txt unless --input is given.
+
+Options:
+  -p, --part <N>        Only run part N
+  -v, --variant <NAME>  Only run the named alternative (`main` for the default)
+  -i, --input <PATH>    Read the input from PATH, or from stdin if PATH is `-`
+  -s, --set <NAME=VAL>  Set a puzzle parameter, e.g. `--set connections=10`
+                        for day 8. May be repeated
+      --all             Run every day and finish with a summary table
+      --json            Print the results as JSON instead
+      --trace           Print a step by step trace of the input instead, for
+                        days that have one: the dial for day 1, the
+                        removal waves for day 4 and a range audit for day 5
+                        (`--set audit=ID,ID` to look up IDs)
+      --progress <MODE> How slow solutions report progress: `bar`, `log` or
+                        `none`. Defaults to a bar when stderr is a terminal
+                        and JSON isn't requested, otherwise none
+  -h, --help            Show this message";
+
+#[derive(Default)]
+struct Options {
+    days: Vec<u32>,
+    part: Option<u32>,
+    variant: Option<String>,
+    input: Option<String>,
+    all: bool,
+    json: bool,
+    trace: bool,
+    progress: Option<String>,
+    settings: Vec<(String, String)>,
+}
+
+#[derive(Clone)]
+enum Status {
+    Solved(Box<Outcome>),
+    Failed(String),
+    MissingInput(String),
+}
+
+struct Record {
+    entry: &'static Entry,
+    status: Status,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{name} needs a value"));
+        match arg.as_str() {
+            "-p" | "--part"    => options.part = Some(value(&arg)?.parse().map_err(|_| "part must be 1 or 2")?),
+            "-v" | "--variant" => options.variant = Some(value(&arg)?),
+            "-i" | "--input"   => options.input = Some(value(&arg)?),
+            "-s" | "--set"     => options.settings.push(parse_setting(&value(&arg)?).map_err(|err| err.to_string())?),
+            "--all"            => options.all = true,
+            "--json"           => options.json = true,
+            "--trace"          => options.trace = true,
+            "--progress"       => options.progress = Some(value(&arg)?),
+            "-h" | "--help"    => return Err(USAGE.to_string()),
+            day => {
+                for day in day.split(',') {
+                    options.days.push(day.parse().map_err(|_| format!("unknown argument `{day}`\n\n{USAGE}"))?);
+                }
+            },
+        }
+    }
+
+    if options.all {
+        options.days = registry().values().map(|entry| entry.day).collect();
+        options.days.dedup();
+    } else if options.days.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    if options.input.is_some() && options.days.len() > 1 {
+        return Err("--input can only be used with a single day".to_string());
+    }
+    for (name, _) in &options.settings {
+        let known = registry()
+            .values()
+            .any(|entry| options.days.contains(&entry.day) && entry.params.contains(&name.as_str()));
+        if !known {
+            return Err(format!("none of the selected days has a parameter `{name}`"));
+        }
+    }
+    if let Some(mode) = &options.progress && !["bar", "log", "none"].contains(&mode.as_str()) {
+        return Err(format!("unknown progress mode `{mode}`, expected bar, log or none"));
+    }
+
+    Ok(options)
+}
+
+fn context(options: &Options) -> Context {
+    let interactive = std::io::stderr().is_terminal() && !options.json;
+    match options.progress.as_deref() {
+        Some("bar")         => Context::with_progress(Bar::default()),
+        Some("log")         => Context::with_progress(Log::default()),
+        None if interactive => Context::with_progress(Bar::default()),
+        _                   => Context::default(),
+    }
+}
+
+fn read_input(day: u32, path: &Option<String>) -> Result<String, Status> {
+    match path.as_deref() {
+        Some("-") => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input).map_err(|err| Status::Failed(err.to_string()))?;
+            Ok(input)
+        },
+        Some(path) => std::fs::read_to_string(path).map_err(|err| Status::Failed(format!("{path}: {err}"))),
+        None => {
+            let path = input_path(day);
+            std::fs::read_to_string(&path).map_err(|_| Status::MissingInput(path))
+        },
+    }
+}
+
+fn selected(entry: &Entry, options: &Options) -> bool {
+    options.days.contains(&entry.day)
+        && options.part.is_none_or(|part| part == entry.part)
+        && options.variant.as_deref().is_none_or(|variant| {
+            entry.variant.unwrap_or("main").eq_ignore_ascii_case(variant)
+        })
+}
+
+fn print_record(record: &Record) {
+    match &record.status {
+        Status::Solved(outcome) => {
+            println!("{}: {}", record.entry.label(), outcome.answer);
+            println!("\tgenerator: {:.2?}, solver: {:.2?}", outcome.generator, outcome.solver);
+            if let (Some(generator), Some(solver)) = (outcome.generator_alloc, outcome.solver_alloc) {
+                println!("\tgenerator heap: {generator}\n\tsolver heap: {solver}");
+            }
+        },
+        Status::Failed(err)        => eprintln!("{}: FAILED\n{err}", record.entry.label()),
+        Status::MissingInput(path) => eprintln!("{}: no input at {path}", record.entry.label()),
+    }
+}
+
+fn print_summary(records: &[Record]) {
+    println!();
+    println!("| Day | Part | Variant     | Answer               | Generator  | Solver     |");
+    println!("|----:|-----:|:------------|:---------------------|-----------:|-----------:|");
+
+    for record in records {
+        let (answer, generator, solver) = match &record.status {
+            Status::Solved(outcome)  => (outcome.answer.to_string(), format!("{:.2?}", outcome.generator), format!("{:.2?}", outcome.solver)),
+            Status::Failed(_)        => ("FAILED".to_string(), String::new(), String::new()),
+            Status::MissingInput(_)  => ("no input".to_string(), String::new(), String::new()),
+        };
+        println!(
+            "| {:>3} | {:>4} | {:<11} | {:<20} | {:>10} | {:>10} |",
+            record.entry.day, record.entry.part, record.entry.variant.unwrap_or(""), answer, generator, solver
+        );
+    }
+
+    let total: std::time::Duration = records
+        .iter()
+        .filter_map(|record| match &record.status {
+            Status::Solved(outcome) => Some(outcome.generator + outcome.solver),
+            _ => None,
+        })
+        .sum();
+    println!("\nTotal: {total:.2?}");
+
+    print_heap_summary(records);
+}
+
+/// Heap use per run, only available with the `alloc-stats` feature.
+fn print_heap_summary(records: &[Record]) {
+    let heap: Vec<(&Entry, AllocStats, AllocStats)> = records
+        .iter()
+        .filter_map(|record| match &record.status {
+            Status::Solved(outcome) => Some((record.entry, outcome.generator_alloc?, outcome.solver_alloc?)),
+            _ => None,
+        })
+        .collect();
+    if heap.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("| Day | Part | Variant     | Gen allocs | Gen peak   | Solver allocs | Solver peak |");
+    println!("|----:|-----:|:------------|-----------:|-----------:|--------------:|------------:|");
+    for (entry, generator, solver) in heap {
+        println!(
+            "| {:>3} | {:>4} | {:<11} | {:>10} | {:>10} | {:>13} | {:>11} |",
+            entry.day, entry.part, entry.variant.unwrap_or(""),
+            generator.allocations, Bytes(generator.peak).to_string(), solver.allocations, Bytes(solver.peak).to_string()
+        );
+    }
+}
+
+fn json_alloc(name: &str, stats: Option<AllocStats>) -> String {
+    stats.map_or(String::new(), |stats| {
+        format!(",\"{name}\":{{\"allocations\":{},\"bytes\":{},\"peak\":{}}}", stats.allocations, stats.bytes, stats.peak)
+    })
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for ch in text.chars() {
+        match ch {
+            '"'  => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            ch if (ch as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+
+    json
+}
+
+fn print_json(records: &[Record]) {
+    let rows: Vec<String> = records
+        .iter()
+        .map(|record| {
+            let variant = record.entry.variant.map_or("null".to_string(), json_string);
+            let status = match &record.status {
+                Status::Solved(outcome) => format!(
+                    "\"answer\":{},\"generator_ns\":{},\"solver_ns\":{}{}{}",
+                    json_string(&outcome.answer.to_string()), outcome.generator.as_nanos(), outcome.solver.as_nanos(),
+                    json_alloc("generator_alloc", outcome.generator_alloc), json_alloc("solver_alloc", outcome.solver_alloc)
+                ),
+                Status::Failed(err)        => format!("\"error\":{}", json_string(err)),
+                Status::MissingInput(path) => format!("\"error\":{}", json_string(&format!("no input at {path}"))),
+            };
+            format!("{{\"day\":{},\"part\":{},\"variant\":{variant},{status}}}", record.entry.day, record.entry.part)
+        })
+        .collect();
+
+    println!("[{}]", rows.join(",\n "));
+}
+
+/// Traces each selected day once, whichever parts were picked.
+fn print_traces(options: &Options, ctx: &Context) -> ExitCode {
+    let mut entries: Vec<&Entry> = registry().values().filter(|entry| selected(entry, options)).collect();
+    entries.dedup_by_key(|entry| entry.day);
+    let mut failed = false;
+
+    for entry in entries {
+        let trace = read_input(entry.day, &options.input)
+            .map_err(|status| match status {
+                Status::MissingInput(path) => format!("no input at {path}"),
+                Status::Failed(err)        => err,
+                Status::Solved(_)          => unreachable!("reading input doesn't solve anything"),
+            })
+            .and_then(|input| entry.trace_with(&input, ctx, &options.settings).map_err(|err| err.to_string()));
+
+        match trace {
+            Ok(Some(trace)) => println!("Day {}\n\n{trace}", entry.day),
+            Ok(None)        => eprintln!("Day {} has no trace", entry.day),
+            Err(err)        => {
+                eprintln!("Day {}: FAILED\n{err}", entry.day);
+                failed = true;
+            },
+        }
+    }
+
+    if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let ctx = context(&options);
+    if options.trace {
+        return print_traces(&options, &ctx);
+    }
+
+    // Each day's input is read once and shared by all of its parts.
+    let mut inputs: HashMap<u32, Result<String, Status>> = HashMap::new();
+    let mut records = Vec::new();
+
+    for entry in registry().values().filter(|entry| selected(entry, &options)) {
+        let input = inputs
+            .entry(entry.day)
+            .or_insert_with(|| read_input(entry.day, &options.input));
+
+        let status = match input {
+            Ok(input) => match entry.run_with(input, &ctx, &options.settings) {
+                Ok(outcome) => Status::Solved(Box::new(outcome)),
+                Err(err) => Status::Failed(err.to_string()),
+            },
+            Err(status) => status.clone(),
+        };
+
+        let record = Record { entry, status };
+        if !options.json {
+            print_record(&record);
+        }
+        records.push(record);
+    }
+
+    if records.is_empty() {
+        eprintln!("No solutions match the selection.");
+        return ExitCode::FAILURE;
+    }
+
+    if options.json {
+        print_json(&records);
+    } else if options.all {
+        print_summary(&records);
+    }
+
+    // Days without an input are only skipped when running the whole year.
+    let failed = records.iter().any(|record| match record.status {
+        Status::Solved(_) => false,
+        Status::Failed(_) => true,
+        Status::MissingInput(_) => !options.all,
+    });
+
+    if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
\ No newline at end of file
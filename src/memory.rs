@@ -0,0 +1,146 @@
+// Heap usage per generator and solver run. With the `alloc-stats` feature,
+// a counting wrapper around the system allocator becomes the global
+// allocator and `measure` reports what a closure allocated. Without it,
+// `measure` just runs the closure.
+//
+// The counters are global, so anything allocating on other threads at the
+// same time (rayon workers included) is counted too.
+use crate::prelude::*;
+
+/// Heap use during one call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Calls to alloc or realloc.
+    pub allocations: u64,
+    /// Bytes asked for, in total. A realloc counts its full new size.
+    pub bytes: u64,
+    /// Highest heap use above where it was when the call started.
+    pub peak: u64,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} allocs, {} allocated, {} peak", self.allocations, Bytes(self.bytes), Bytes(self.peak))
+    }
+}
+
+/// A byte count, shown in B, KiB or MiB.
+pub struct Bytes(pub u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            bytes if bytes < 1 << 10 => write!(f, "{bytes} B"),
+            bytes if bytes < 1 << 20 => write!(f, "{:.1} KiB", bytes as f64 / 1024.0),
+            bytes                    => write!(f, "{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use super::AllocStats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+    static CURRENT: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+
+    pub struct Counting;
+
+    impl Counting {
+        fn record(&self, size: usize) {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            BYTES.fetch_add(size as u64, Relaxed);
+            let current = CURRENT.fetch_add(size as u64, Relaxed) + size as u64;
+            PEAK.fetch_max(current, Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                self.record(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                self.record(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            CURRENT.fetch_sub(layout.size() as u64, Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                CURRENT.fetch_sub(layout.size() as u64, Relaxed);
+                self.record(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    pub fn measure<T>(run: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+        let (allocations, bytes) = (ALLOCATIONS.load(Relaxed), BYTES.load(Relaxed));
+        let start = CURRENT.load(Relaxed);
+        PEAK.store(start, Relaxed);
+
+        let result = run();
+
+        let stats = AllocStats {
+            allocations: ALLOCATIONS.load(Relaxed) - allocations,
+            bytes: BYTES.load(Relaxed) - bytes,
+            peak: PEAK.load(Relaxed).saturating_sub(start),
+        };
+
+        (result, Some(stats))
+    }
+}
+
+/// Runs `run`, returning its result and what it allocated, or `None` for
+/// the stats when built without the `alloc-stats` feature.
+pub fn measure<T>(run: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    return counting::measure(run);
+
+    #[cfg(not(feature = "alloc-stats"))]
+    (run(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measure_test() {
+        let (vec, stats) = measure(|| Vec::<u8>::with_capacity(4096));
+        assert_eq!(vec.capacity(), 4096);
+
+        if cfg!(feature = "alloc-stats") {
+            let stats = stats.unwrap();
+            assert!(stats.allocations >= 1 && stats.bytes >= 4096);
+        } else {
+            assert_eq!(stats, None);
+        }
+    }
+
+    #[test]
+    fn display_test() {
+        let stats = AllocStats { allocations: 3, bytes: 1536, peak: 3 << 20 };
+        assert_eq!(stats.to_string(), "3 allocs, 1.5 KiB allocated, 3.0 MiB peak");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+// Puzzle parameters that the real inputs take for granted, like day 8's 1000
+// connections, as one typed struct per day. Defaults match the real puzzle;
+// tests set the smaller values the examples use, and the CLI sets them by
+// name with `--set name=value`.
+use crate::prelude::*;
+
+pub trait Params: Default + Clone + fmt::Debug + Send + Sync + 'static {
+    /// Every name `set` accepts.
+    const NAMES: &'static [&'static str];
+
+    /// Sets one parameter from text. On failure, returns a description of
+    /// what was expected.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    /// Checks the parameters make sense together once they're all set, so a
+    /// bad setting is reported rather than panicking in the solver. On
+    /// failure, returns a description of what was expected.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// For days without any parameters.
+impl Params for () {
+    const NAMES: &'static [&'static str] = &[];
+
+    fn set(&mut self, _name: &str, _value: &str) -> Result<(), String> {
+        Err("no parameters".to_string())
+    }
+}
+
+/// Implements `Params` for a struct, with one name per listed field, and
+/// optionally a function to validate them with:
+///
+/// ```ignore
+/// params!(Params { connections });
+/// params!(Params { positions, start }, validate = Params::check);
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($type:ident { $($field:ident),+ $(,)? }) => {
+        $crate::params!($type { $($field),+ }, validate = |_| Ok(()));
+    };
+    ($type:ident { $($field:ident),+ $(,)? }, validate = $validate:expr) => {
+        impl $crate::params::Params for $type {
+            const NAMES: &'static [&'static str] = &[$(stringify!($field)),+];
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                match name {
+                    $(stringify!($field) => self.$field = $crate::prelude::FromField::from_field(value)?,)+
+                    _ => return Err(format!("one of {}", Self::NAMES.join(", "))),
+                }
+
+                Ok(())
+            }
+
+            fn validate(&self) -> Result<(), String> {
+                let validate: fn(&Self) -> Result<(), String> = $validate;
+                validate(self)
+            }
+        }
+    };
+}
+
+/// Builds a day's parameters from (name, value) settings, skipping names it
+/// doesn't have so that one list of settings can serve several days.
+/// Fails on the first setting that doesn't parse, or on all of them together
+/// if they don't validate.
+pub fn from_settings<P: Params>(settings: &[(String, String)]) -> Result<P, ParseError> {
+    let mut params = P::default();
+    let mut applied = Vec::new();
+
+    for (name, value) in settings.iter().filter(|(name, _)| P::NAMES.contains(&name.as_str())) {
+        params
+            .set(name, value)
+            .map_err(|expected| ParseError::new(&format!("{name}={value}"), name.len() + 2, expected, value.as_str()))?;
+        applied.push(format!("{name}={value}"));
+    }
+
+    params.validate().map_err(|expected| {
+        let text = applied.join(" ");
+        ParseError::new(&text, 1, expected, text.as_str())
+    })?;
+
+    Ok(params)
+}
+
+/// Splits a `name=value` setting.
+pub fn parse_setting(setting: &str) -> Result<(String, String), ParseError> {
+    let (name, value) = parse_line!(setting, "{}={}" => String, String)?;
+
+    Ok((name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    struct Example {
+        size: usize,
+        start: isize,
+    }
+
+    impl Example {
+        fn check(&self) -> Result<(), String> {
+            if self.size > 100 {
+                return Err("size of at most 100".to_string());
+            }
+
+            Ok(())
+        }
+    }
+
+    params!(Example { size, start }, validate = Example::check);
+
+    #[test]
+    fn settings_test() {
+        let settings = vec![parse_setting("start=-5").unwrap(), parse_setting("other=1").unwrap()];
+        let params: Example = from_settings(&settings).unwrap();
+        assert_eq!((params.size, params.start), (0, -5));
+
+        let err = from_settings::<Example>(&[parse_setting("size=big").unwrap()]).unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (6, "big"));
+        assert!(parse_setting("size").is_err());
+        assert_eq!(Example::NAMES, ["size", "start"]);
+
+        let settings = vec![parse_setting("start=1").unwrap(), parse_setting("size=101").unwrap()];
+        let err = from_settings::<Example>(&settings).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str(), err.found.as_str()), (1, "size of at most 100", "start=1 size=101"));
+    }
+}
\ No newline at end of file
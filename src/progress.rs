@@ -0,0 +1,144 @@
+// Progress reporting for the slower solutions. A solver gets a `Progress`
+// through its `Context` and reports steps to it, from whichever rayon thread
+// finished them. Whether that draws a bar, logs a line now and then or does
+// nothing at all is up to whoever runs the solver.
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::iter::ParallelIterator;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+pub trait Progress: Send + Sync {
+    /// Begins a task of `total` steps, replacing any previous one.
+    fn start(&self, label: &str, total: u64);
+
+    /// Marks `steps` more as done. Called from any thread.
+    fn advance(&self, steps: u64);
+
+    fn finish(&self);
+}
+
+/// Reports nothing. The default, so tests and scripted runs stay quiet.
+pub struct Silent;
+
+impl Progress for Silent {
+    fn start(&self, _label: &str, _total: u64) {}
+    fn advance(&self, _steps: u64) {}
+    fn finish(&self) {}
+}
+
+/// A progress bar on stderr.
+pub struct Bar(Mutex<ProgressBar>);
+
+impl Default for Bar {
+    fn default() -> Self {
+        Self(Mutex::new(ProgressBar::hidden()))
+    }
+}
+
+impl Progress for Bar {
+    fn start(&self, label: &str, total: u64) {
+        let bar = ProgressBar::new(total).with_message(label.to_string());
+        bar.set_style(ProgressStyle::default_bar()
+            .template("{msg} [{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} ({eta})").unwrap());
+        *self.0.lock().unwrap() = bar;
+    }
+
+    fn advance(&self, steps: u64) {
+        self.0.lock().unwrap().inc(steps);
+    }
+
+    fn finish(&self) {
+        self.0.lock().unwrap().finish_and_clear();
+    }
+}
+
+/// Writes a line to stderr at most once per interval, for logs and CI where
+/// a bar would only make a mess.
+pub struct Log {
+    every: Duration,
+    done: AtomicU64,
+    task: Mutex<LogTask>,
+}
+
+struct LogTask {
+    label: String,
+    total: u64,
+    started: Instant,
+    logged: Instant,
+}
+
+impl Log {
+    pub fn every(every: Duration) -> Self {
+        let now = Instant::now();
+        let task = LogTask { label: String::new(), total: 0, started: now, logged: now };
+
+        Self { every, done: AtomicU64::new(0), task: Mutex::new(task) }
+    }
+}
+
+impl Default for Log {
+    fn default() -> Self {
+        Self::every(Duration::from_secs(1))
+    }
+}
+
+impl Progress for Log {
+    fn start(&self, label: &str, total: u64) {
+        let now = Instant::now();
+        *self.task.lock().unwrap() = LogTask { label: label.to_string(), total, started: now, logged: now };
+        self.done.store(0, Ordering::Relaxed);
+    }
+
+    fn advance(&self, steps: u64) {
+        let done = self.done.fetch_add(steps, Ordering::Relaxed) + steps;
+
+        // Whoever holds the lock is about to log anyway, so don't wait on it.
+        if let Ok(mut task) = self.task.try_lock() && task.logged.elapsed() >= self.every {
+            task.logged = Instant::now();
+            let percent = done as f64 * 100.0 / task.total.max(1) as f64;
+            eprintln!("{}: {done}/{} ({percent:.0}%) after {:.1?}", task.label, task.total, task.started.elapsed());
+        }
+    }
+
+    fn finish(&self) {
+        let task = self.task.lock().unwrap();
+        eprintln!("{}: done in {:.2?}", task.label, task.started.elapsed());
+    }
+}
+
+/// Reports a step to `progress` for every item of a parallel iterator.
+pub trait ParallelProgress: ParallelIterator {
+    fn report(self, progress: &dyn Progress) -> impl ParallelIterator<Item = Self::Item> {
+        self.inspect(move |_| progress.advance(1))
+    }
+}
+
+impl<I: ParallelIterator> ParallelProgress for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+
+    #[derive(Default)]
+    struct Count(AtomicU64);
+
+    impl Progress for Count {
+        fn start(&self, _label: &str, _total: u64) {}
+
+        fn advance(&self, steps: u64) {
+            self.0.fetch_add(steps, Ordering::Relaxed);
+        }
+
+        fn finish(&self) {}
+    }
+
+    #[test]
+    fn report_test() {
+        let count = Count::default();
+        let sum: u64 = (1..=1000u64).into_par_iter().report(&count).sum();
+        assert_eq!(sum, 500500);
+        assert_eq!(count.0.load(Ordering::Relaxed), 1000);
+    }
+}
\ No newline at end of file
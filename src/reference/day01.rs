@@ -0,0 +1,62 @@
+use super::*;
+
+fn rotations(input: &str) -> impl Iterator<Item = (i64, usize)> + '_ {
+    input.lines().map(|line| {
+        let step = if line.starts_with('L') { -1 } else { 1 };
+        (step, line[1..].trim().parse().unwrap())
+    })
+}
+
+/// Rotations that leave the dial at zero.
+pub fn part1(input: &str) -> Answer {
+    let mut dial = 50;
+    let mut count = 0;
+
+    for (step, clicks) in rotations(input) {
+        dial = (dial + step * clicks as i64).rem_euclid(100);
+        if dial == 0 {
+            count += 1;
+        }
+    }
+
+    count.into()
+}
+
+/// Clicks that land on zero, turning the dial one click at a time.
+pub fn part2(input: &str) -> Answer {
+    let mut dial = 50;
+    let mut count = 0;
+
+    for (step, clicks) in rotations(input) {
+        for _ in 0..clicks {
+            dial = (dial + step).rem_euclid(100);
+            if dial == 0 {
+                count += 1;
+            }
+        }
+    }
+
+    count.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::Day01;
+    use crate::generate::{Generator, Rotations};
+
+    const TEST: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    #[test]
+    fn reference_test() {
+        assert_eq!(part1(TEST), Answer::Int(3));
+        assert_eq!(part2(TEST), Answer::Int(6));
+    }
+
+    #[test]
+    fn differential_test() {
+        let input = |case| Rotations { rotations: 1 + case as usize % 30, max_steps: 250 }.generate_seeded(case);
+        assert_agrees::<Day01>(1, part1, 2000, input);
+        assert_agrees::<Day01>(2, part2, 2000, input);
+    }
+}
@@ -0,0 +1,41 @@
+use super::*;
+
+fn ids(input: &str) -> impl Iterator<Item = u64> + '_ {
+    input.trim().split(',').flat_map(|range| {
+        let (start, end) = range.split_once('-').unwrap();
+        start.parse().unwrap()..=end.parse().unwrap()
+    })
+}
+
+/// Whether the ID is a block of digits repeated at least `min_repeats` times,
+/// and at most `max_repeats` times.
+fn repeated(id: u64, min_repeats: usize, max_repeats: usize) -> bool {
+    let digits = id.to_string();
+    (min_repeats..=min(max_repeats, digits.len()))
+        .filter(|&repeats| digits.len().is_multiple_of(repeats))
+        .any(|repeats| digits[..digits.len() / repeats].repeat(repeats) == digits)
+}
+
+/// IDs made of a block repeated exactly twice.
+pub fn part1(input: &str) -> Answer {
+    ids(input).filter(|&id| repeated(id, 2, 2)).sum::<u64>().into()
+}
+
+/// IDs made of a block repeated any number of times.
+pub fn part2(input: &str) -> Answer {
+    ids(input).filter(|&id| repeated(id, 2, usize::MAX)).sum::<u64>().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day02::Day02;
+    use crate::generate::{Generator, IdRanges};
+
+    #[test]
+    fn differential_test() {
+        let input = |case| IdRanges { ranges: 1 + case as usize % 8, max_digits: 7, max_span: 2000 }.generate_seeded(case);
+        assert_agrees::<Day02>(1, part1, 1000, input);
+        assert_agrees::<Day02>(2, part2, 1000, input);
+    }
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+/// Largest number made from `k` of the digits, kept in order, trying every
+/// choice of digits.
+fn best(digits: &[u64], k: usize) -> u64 {
+    if k == 0 {
+        return 0;
+    }
+
+    (0..=digits.len() - k)
+        .map(|idx| digits[idx] * 10_u64.pow(k as u32 - 1) + best(&digits[idx + 1..], k - 1))
+        .max()
+        .unwrap()
+}
+
+fn joltage(input: &str, k: usize) -> Answer {
+    input
+        .lines()
+        .map(|line| {
+            let digits: Vec<u64> = line.trim().chars().map(|ch| ch.to_digit(10).unwrap() as u64).collect();
+            best(&digits, k)
+        })
+        .sum::<u64>()
+        .into()
+}
+
+pub fn part1(input: &str) -> Answer {
+    joltage(input, 2)
+}
+
+pub fn part2(input: &str) -> Answer {
+    joltage(input, 12)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day03::Day03;
+    use crate::generate::{BatteryBanks, Generator};
+
+    #[test]
+    fn differential_test() {
+        // Every choice of twelve from sixteen is only 1820 numbers.
+        let input = |case| BatteryBanks { banks: 1 + case as usize % 5, batteries: 12 + case as usize % 5 }.generate_seeded(case);
+        assert_agrees::<Day03>(1, part1, 1000, input);
+        assert_agrees::<Day03>(2, part2, 1000, input);
+    }
+}
@@ -0,0 +1,59 @@
+use super::*;
+
+fn grid(input: &str) -> Vec<Vec<bool>> {
+    input.lines().map(|line| line.trim().chars().map(|ch| ch == '@').collect()).collect()
+}
+
+/// A roll that has fewer than four rolls in the eight cells around it.
+fn accessible(grid: &[Vec<bool>], row: usize, col: usize) -> bool {
+    let mut neighbours = 0;
+    for r in row.saturating_sub(1)..=row + 1 {
+        for c in col.saturating_sub(1)..=col + 1 {
+            if (r, c) != (row, col) && grid.get(r).and_then(|line| line.get(c)) == Some(&true) {
+                neighbours += 1;
+            }
+        }
+    }
+
+    grid[row][col] && neighbours < 4
+}
+
+fn cells(grid: &[Vec<bool>]) -> Vec<(usize, usize)> {
+    (0..grid.len()).flat_map(|row| (0..grid[row].len()).map(move |col| (row, col))).collect()
+}
+
+pub fn part1(input: &str) -> Answer {
+    let grid = grid(input);
+    cells(&grid).into_iter().filter(|&(row, col)| accessible(&grid, row, col)).count().into()
+}
+
+/// Removes accessible rolls one at a time, rescanning the whole grid after
+/// each, until none are left.
+pub fn part2(input: &str) -> Answer {
+    let mut grid = grid(input);
+    let mut removed = 0;
+
+    while let Some((row, col)) = cells(&grid).into_iter().find(|&(row, col)| accessible(&grid, row, col)) {
+        grid[row][col] = false;
+        removed += 1;
+    }
+
+    removed.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day04::Day04;
+    use crate::generate::{Generator, PaperGrid};
+
+    #[test]
+    fn differential_test() {
+        let input = |case| {
+            let (width, height) = (1 + case as usize % 9, 1 + case as usize / 9 % 9);
+            PaperGrid { width, height, density: 0.7 }.generate_seeded(case)
+        };
+        assert_agrees::<Day04>(1, part1, 1000, input);
+        assert_agrees::<Day04>(2, part2, 1000, input);
+    }
+}
\ No newline at end of file
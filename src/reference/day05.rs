@@ -0,0 +1,47 @@
+use super::*;
+
+fn inventory(input: &str) -> (Vec<(u64, u64)>, Vec<u64>) {
+    let (ranges, ids) = input.split_once("\n\n").unwrap();
+    let ranges = ranges
+        .lines()
+        .map(|line| {
+            let (start, end) = line.trim().split_once('-').unwrap();
+            (start.parse().unwrap(), end.parse().unwrap())
+        })
+        .collect();
+    let ids = ids.lines().map(|line| line.trim().parse().unwrap()).collect();
+
+    (ranges, ids)
+}
+
+/// Available IDs that fall in any fresh range.
+pub fn part1(input: &str) -> Answer {
+    let (ranges, ids) = inventory(input);
+    ids.iter()
+        .filter(|&&id| ranges.iter().any(|&(start, end)| start <= id && id <= end))
+        .count()
+        .into()
+}
+
+/// Every fresh ID, counted once. Only for small IDs.
+pub fn part2(input: &str) -> Answer {
+    let (ranges, _) = inventory(input);
+    ranges.iter().flat_map(|&(start, end)| start..=end).collect::<HashSet<_>>().len().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day05::Day05;
+    use crate::generate::{Generator, Inventory};
+
+    #[test]
+    fn differential_test() {
+        let input = |case| {
+            let size = 1 + case as usize % 10;
+            Inventory { ranges: size, ids: 2 * size, max_id: 200, max_span: 40 }.generate_seeded(case)
+        };
+        assert_agrees::<Day05>(1, part1, 2000, input);
+        assert_agrees::<Day05>(2, part2, 2000, input);
+    }
+}
@@ -0,0 +1,72 @@
+use super::*;
+
+/// Splits the worksheet into problems at the columns that are all spaces,
+/// giving each problem's rows of text and its operator.
+fn problems(input: &str) -> Vec<(Vec<String>, char)> {
+    let lines: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let width = lines.iter().map(Vec::len).max().unwrap();
+    let at = |row: usize, col: usize| lines[row].get(col).copied().unwrap_or(' ');
+    let blank = |col: usize| (0..lines.len()).all(|row| at(row, col) == ' ');
+
+    let mut problems = Vec::new();
+    let mut start = 0;
+    for col in 0..=width {
+        if col == width || blank(col) {
+            if col > start {
+                let rows = (0..lines.len() - 1).map(|row| (start..col).map(|c| at(row, c)).collect()).collect();
+                let oper = (start..col).map(|c| at(lines.len() - 1, c)).find(|&ch| ch != ' ').unwrap();
+                problems.push((rows, oper));
+            }
+            start = col + 1;
+        }
+    }
+
+    problems
+}
+
+fn total(problems: Vec<(Vec<String>, char)>) -> Answer {
+    problems
+        .into_iter()
+        .map(|(numbers, oper)| {
+            let numbers = numbers.iter().map(|number| number.trim().parse::<u64>().unwrap());
+            if oper == '+' { numbers.sum::<u64>() } else { numbers.product() }
+        })
+        .sum::<u64>()
+        .into()
+}
+
+/// Numbers read across each row.
+pub fn part1(input: &str) -> Answer {
+    total(problems(input))
+}
+
+/// Numbers read down each column, from right to left.
+pub fn part2(input: &str) -> Answer {
+    let columns = problems(input)
+        .into_iter()
+        .map(|(rows, oper)| {
+            let width = rows[0].chars().count();
+            let numbers = (0..width)
+                .rev()
+                .map(|col| rows.iter().filter_map(|row| row.chars().nth(col)).filter(|&ch| ch != ' ').collect())
+                .collect();
+            (numbers, oper)
+        })
+        .collect();
+
+    total(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day06::Day06;
+    use crate::generate::{Generator, Worksheet};
+
+    #[test]
+    fn differential_test() {
+        let input = |case| Worksheet { problems: 1 + case as usize % 10, rows: 1 + case as usize % 4, max_digits: 4 }.generate_seeded(case);
+        assert_agrees::<Day06>(1, part1, 1000, input);
+        assert_agrees::<Day06>(2, part2, 1000, input);
+    }
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+fn manifold(input: &str) -> (usize, Vec<Vec<bool>>) {
+    let mut lines = input.lines();
+    let start = lines.next().unwrap().find('S').unwrap();
+    let splitters = lines.map(|line| line.chars().map(|ch| ch == '^').collect()).collect();
+
+    (start, splitters)
+}
+
+/// Follows every beam down the manifold a row at a time, counting the
+/// splitters that get hit.
+pub fn part1(input: &str) -> Answer {
+    let (start, splitters) = manifold(input);
+    let mut beams = vec![start];
+    let mut splits = 0;
+
+    for row in &splitters {
+        let mut next = Vec::new();
+        for &beam in &beams {
+            if row[beam] {
+                splits += 1;
+                next.extend([beam - 1, beam + 1]);
+            } else {
+                next.push(beam);
+            }
+        }
+        next.sort_unstable();
+        next.dedup();
+        beams = next;
+    }
+
+    splits.into()
+}
+
+/// Walks every single timeline to the bottom, without merging any.
+pub fn part2(input: &str) -> Answer {
+    fn timelines(splitters: &[Vec<bool>], beam: usize) -> u64 {
+        match splitters.split_first() {
+            None => 1,
+            Some((row, rest)) if row[beam] => timelines(rest, beam - 1) + timelines(rest, beam + 1),
+            Some((_, rest)) => timelines(rest, beam),
+        }
+    }
+
+    let (start, splitters) = manifold(input);
+    timelines(&splitters, start).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day07::Day07;
+    use crate::generate::{Generator, Manifold};
+
+    #[test]
+    fn differential_test() {
+        // Timelines double at each row, so keep to a dozen rows.
+        let input = |case| {
+            let rows = 1 + case as usize % 12;
+            Manifold { width: 2 * rows + 1 + case as usize % 3, rows, density: 0.7 }.generate_seeded(case)
+        };
+        assert_agrees::<Day07>(1, part1, 1000, input);
+        assert_agrees::<Day07>(2, part2, 1000, input);
+    }
+}
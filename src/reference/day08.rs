@@ -0,0 +1,87 @@
+use super::*;
+
+fn boxes(input: &str) -> Vec<[i64; 3]> {
+    input
+        .lines()
+        .map(|line| {
+            let coords: Vec<i64> = line.trim().split(',').map(|coord| coord.parse().unwrap()).collect();
+            [coords[0], coords[1], coords[2]]
+        })
+        .collect()
+}
+
+/// Every pair of boxes, closest first.
+fn pairs(boxes: &[[i64; 3]]) -> Vec<(usize, usize)> {
+    let distance = |&(a, b): &(usize, usize)| (0..3).map(|axis| (boxes[a][axis] - boxes[b][axis]).pow(2)).sum::<i64>();
+    let mut pairs: Vec<(usize, usize)> = (0..boxes.len()).flat_map(|a| (a + 1..boxes.len()).map(move |b| (a, b))).collect();
+    pairs.sort_by_key(|pair| (distance(pair), *pair));
+
+    pairs
+}
+
+/// Circuit sizes, found by walking the connections from each box.
+fn circuits(len: usize, connections: &[(usize, usize)]) -> Vec<usize> {
+    let mut seen = vec![false; len];
+    let mut sizes = Vec::new();
+
+    for first in 0..len {
+        if seen[first] {
+            continue;
+        }
+        seen[first] = true;
+        let mut stack = vec![first];
+        let mut size = 0;
+        while let Some(current) = stack.pop() {
+            size += 1;
+            for &(a, b) in connections {
+                for (from, to) in [(a, b), (b, a)] {
+                    if from == current && !seen[to] {
+                        seen[to] = true;
+                        stack.push(to);
+                    }
+                }
+            }
+        }
+        sizes.push(size);
+    }
+
+    sizes
+}
+
+/// Product of the three largest circuits after the thousand closest pairs
+/// are connected.
+pub fn part1(input: &str) -> Answer {
+    let boxes = boxes(input);
+    let pairs = pairs(&boxes);
+    let mut sizes = circuits(boxes.len(), &pairs[..1000]);
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+    sizes.iter().take(3).product::<usize>().into()
+}
+
+/// Connects pairs until there is a single circuit, then multiplies the X
+/// coordinates of the last two boxes joined.
+pub fn part2(input: &str) -> Answer {
+    let boxes = boxes(input);
+    let pairs = pairs(&boxes);
+    let last = (1..=pairs.len()).find(|&count| circuits(boxes.len(), &pairs[..count]).len() == 1).unwrap();
+    let (a, b) = pairs[last - 1];
+
+    (boxes[a][0] * boxes[b][0]).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day08::Day08;
+    use crate::generate::{Generator, JunctionBoxes};
+
+    #[test]
+    fn differential_test() {
+        // Part 1 makes a thousand connections, so it needs at least 46 boxes.
+        let input = |case| JunctionBoxes { boxes: 46 + case as usize % 10, max_coord: 10_000 }.generate_seeded(case);
+        assert_agrees::<Day08>(1, part1, 200, input);
+        let input = |case| JunctionBoxes { boxes: 2 + case as usize % 30, max_coord: 10_000 }.generate_seeded(case);
+        assert_agrees::<Day08>(2, part2, 1000, input);
+    }
+}
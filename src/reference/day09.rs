@@ -0,0 +1,73 @@
+use super::*;
+
+fn corners(input: &str) -> Vec<(i64, i64)> {
+    input
+        .lines()
+        .map(|line| {
+            let (x, y) = line.trim().split_once(',').unwrap();
+            (x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect()
+}
+
+fn area((ax, ay): (i64, i64), (bx, by): (i64, i64)) -> i64 {
+    ((ax - bx).abs() + 1) * ((ay - by).abs() + 1)
+}
+
+/// Whether a tile is on the loop or inside it, by casting a ray to the right
+/// and counting the vertical edges it crosses.
+fn red_or_green(corners: &[(i64, i64)], (x, y): (i64, i64)) -> bool {
+    let edges = corners.iter().zip(corners.iter().cycle().skip(1));
+    let mut crossings = 0;
+
+    for (&(ax, ay), &(bx, by)) in edges {
+        if (min(ax, bx)..=max(ax, bx)).contains(&x) && (min(ay, by)..=max(ay, by)).contains(&y) {
+            return true;
+        }
+        if ax == bx && ax > x && min(ay, by) <= y && y < max(ay, by) {
+            crossings += 1;
+        }
+    }
+
+    crossings % 2 == 1
+}
+
+/// Largest rectangle with red tiles at opposite corners.
+pub fn part1(input: &str) -> Answer {
+    let corners = corners(input);
+    corners.iter().flat_map(|&a| corners.iter().map(move |&b| area(a, b))).max().unwrap().into()
+}
+
+/// Largest such rectangle made only of red and green tiles, checking every
+/// tile in it. Only for small coordinates.
+pub fn part2(input: &str) -> Answer {
+    let corners = corners(input);
+    let mut best = 0;
+
+    for &a in &corners {
+        for &b in &corners {
+            if area(a, b) > best
+                && (min(a.0, b.0)..=max(a.0, b.0))
+                    .all(|x| (min(a.1, b.1)..=max(a.1, b.1)).all(|y| red_or_green(&corners, (x, y))))
+            {
+                best = area(a, b);
+            }
+        }
+    }
+
+    best.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day09::Day09;
+    use crate::generate::{Generator, TileLoop};
+
+    #[test]
+    fn differential_test() {
+        let input = |case| TileLoop { columns: 1 + case as usize % 6, max_coord: 60, max_step: 4, min_gap: 1 }.generate_seeded(case);
+        assert_agrees::<Day09>(1, part1, 1000, input);
+        assert_agrees::<Day09>(2, part2, 1000, input);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+use super::*;
+
+struct Machine {
+    lights: Vec<bool>,
+    buttons: Vec<Vec<usize>>,
+    joltage: Vec<usize>,
+}
+
+fn machines(input: &str) -> Vec<Machine> {
+    input
+        .lines()
+        .map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let inner = |field: &str| field[1..field.len() - 1].to_string();
+            let numbers = |field: &str| -> Vec<usize> { inner(field).split(',').map(|n| n.parse().unwrap()).collect() };
+
+            Machine {
+                lights: inner(fields[0]).chars().map(|ch| ch == '#').collect(),
+                buttons: fields[1..fields.len() - 1].iter().map(|field| numbers(field)).collect(),
+                joltage: numbers(fields[fields.len() - 1]),
+            }
+        })
+        .collect()
+}
+
+/// Tries every set of buttons, each pressed once at most.
+pub fn part1(input: &str) -> Answer {
+    machines(input)
+        .iter()
+        .map(|machine| {
+            (0_u32..1 << machine.buttons.len())
+                .filter(|&pressed| {
+                    let mut lights = vec![false; machine.lights.len()];
+                    for (idx, button) in machine.buttons.iter().enumerate() {
+                        if pressed & (1 << idx) != 0 {
+                            button.iter().for_each(|&light| lights[light] = !lights[light]);
+                        }
+                    }
+                    lights == machine.lights
+                })
+                .map(u32::count_ones)
+                .min()
+                .unwrap() as usize
+        })
+        .sum::<usize>()
+        .into()
+}
+
+/// Tries every number of presses for every button, up to the point where a
+/// counter would overshoot.
+pub fn part2(input: &str) -> Answer {
+    fn fewest(buttons: &[Vec<usize>], remaining: &mut [usize]) -> Option<usize> {
+        let Some((button, rest)) = buttons.split_first() else {
+            return remaining.iter().all(|&left| left == 0).then_some(0);
+        };
+
+        let most = button.iter().map(|&light| remaining[light]).min().unwrap();
+        let mut best = None;
+        for presses in 0..=most {
+            button.iter().for_each(|&light| remaining[light] -= presses);
+            if let Some(more) = fewest(rest, remaining) {
+                best = Some(best.map_or(presses + more, |best: usize| best.min(presses + more)));
+            }
+            button.iter().for_each(|&light| remaining[light] += presses);
+        }
+
+        best
+    }
+
+    machines(input)
+        .iter()
+        .map(|machine| fewest(&machine.buttons, &mut machine.joltage.clone()).unwrap())
+        .sum::<usize>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day10::Day10;
+    use crate::generate::{Generator, Machines};
+
+    const TEST: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+
+    #[test]
+    fn reference_test() {
+        assert_eq!(part1(TEST), Answer::Int(7));
+        assert_eq!(part2(TEST), Answer::Int(33));
+    }
+
+    fn input(case: u64) -> String {
+        Machines { machines: 1 + case as usize % 4, max_lights: 6, max_presses: 3 }.generate_seeded(case)
+    }
+
+    #[test]
+    fn part1_differential_test() {
+        assert_agrees::<Day10>(1, part1, 1000, input);
+    }
+
+    // Part 2 hands each machine to z3, so fewer cases.
+    #[test]
+    fn part2_differential_test() {
+        assert_agrees::<Day10>(2, part2, 200, input);
+    }
+}
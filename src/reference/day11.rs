@@ -0,0 +1,55 @@
+use super::*;
+
+fn devices(input: &str) -> HashMap<&str, Vec<&str>> {
+    input
+        .lines()
+        .map(|line| {
+            let (name, outputs) = line.split_once(": ").unwrap();
+            (name, outputs.split_whitespace().collect())
+        })
+        .collect()
+}
+
+/// Every path from `from` to `out`, listed in full.
+fn paths<'a>(devices: &HashMap<&'a str, Vec<&'a str>>, from: &'a str) -> Vec<Vec<&'a str>> {
+    if from == "out" {
+        return vec![vec!["out"]];
+    }
+
+    devices[from]
+        .iter()
+        .flat_map(|&next| paths(devices, next))
+        .map(|mut path| {
+            path.insert(0, from);
+            path
+        })
+        .collect()
+}
+
+pub fn part1(input: &str) -> Answer {
+    paths(&devices(input), "you").len().into()
+}
+
+/// Paths from `svr` that pass through both `dac` and `fft`, in either order.
+pub fn part2(input: &str) -> Answer {
+    paths(&devices(input), "svr")
+        .iter()
+        .filter(|path| path.contains(&"dac") && path.contains(&"fft"))
+        .count()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day11::Day11;
+    use crate::generate::{DeviceGraph, Generator};
+
+    #[test]
+    fn differential_test() {
+        // Every path is listed, so the graph has to stay small.
+        let input = |case| DeviceGraph { layers: 3 + case as usize % 5, width: 2 + case as usize % 4, max_outputs: 3, reach: 3 }.generate_seeded(case);
+        assert_agrees::<Day11>(1, part1, 1000, input);
+        assert_agrees::<Day11>(2, part2, 1000, input);
+    }
+}
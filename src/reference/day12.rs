@@ -0,0 +1,151 @@
+use super::*;
+
+type Shape = Vec<(usize, usize)>;
+/// Width, height and how many of each shape.
+type Region = (usize, usize, Vec<usize>);
+
+fn puzzle(input: &str) -> (Vec<Shape>, Vec<Region>) {
+    let blocks: Vec<&str> = input.split("\n\n").collect();
+    let (regions, shapes) = blocks.split_last().unwrap();
+    let shapes = shapes
+        .iter()
+        .map(|block| {
+            block
+                .lines()
+                .skip(1)
+                .enumerate()
+                .flat_map(|(row, line)| line.chars().enumerate().filter(|&(_, ch)| ch == '#').map(move |(col, _)| (row, col)))
+                .collect()
+        })
+        .collect();
+    let regions = regions
+        .lines()
+        .map(|line| {
+            let (size, counts) = line.split_once(": ").unwrap();
+            let (width, height) = size.split_once('x').unwrap();
+            (width.parse().unwrap(), height.parse().unwrap(), counts.split_whitespace().map(|n| n.parse().unwrap()).collect())
+        })
+        .collect();
+
+    (shapes, regions)
+}
+
+/// The distinct rotations and reflections of a shape, moved to the origin.
+fn orientations(shape: &Shape) -> Vec<Shape> {
+    let mut all: Vec<Shape> = Vec::new();
+    let mut current = shape.clone();
+
+    for flip in 0..2 {
+        for _ in 0..4 {
+            current = current.iter().map(|&(row, col)| (col, 2 - row)).collect();
+            let shifted = if flip == 0 { current.clone() } else { current.iter().map(|&(row, col)| (row, 2 - col)).collect() };
+            let (top, left) = (shifted.iter().map(|c| c.0).min().unwrap(), shifted.iter().map(|c| c.1).min().unwrap());
+            let mut normal: Shape = shifted.iter().map(|&(row, col)| (row - top, col - left)).collect();
+            normal.sort_unstable();
+            if !all.contains(&normal) {
+                all.push(normal);
+            }
+        }
+    }
+
+    all
+}
+
+/// Tries every placement of every present, remembering the layouts that
+/// have already failed. `used` has a bit per cell, 64 to a word, so regions
+/// can be any size.
+fn fits(shapes: &[Vec<Shape>], width: usize, height: usize, presents: &mut Vec<usize>, used: &mut Vec<u64>, failed: &mut HashSet<(Vec<u64>, Vec<usize>)>) -> bool {
+    let Some(shape) = presents.pop() else {
+        return true;
+    };
+    let key = (used.clone(), presents.clone());
+    let bit = |cell: usize| (cell / 64, 1_u64 << (cell % 64));
+
+    let mut found = false;
+    if !failed.contains(&key) {
+        'search: for orientation in &shapes[shape] {
+            for top in 0..height {
+                for left in 0..width {
+                    let cells: Option<Vec<usize>> = orientation
+                        .iter()
+                        .map(|&(row, col)| (top + row < height && left + col < width).then_some((top + row) * width + left + col))
+                        .collect();
+                    let Some(cells) = cells else {
+                        continue;
+                    };
+                    if cells.iter().any(|&cell| used[bit(cell).0] & bit(cell).1 != 0) {
+                        continue;
+                    }
+
+                    let flip = |used: &mut Vec<u64>| cells.iter().for_each(|&cell| used[bit(cell).0] ^= bit(cell).1);
+                    flip(used);
+                    found = fits(shapes, width, height, presents, used, failed);
+                    flip(used);
+                    if found {
+                        break 'search;
+                    }
+                }
+            }
+        }
+    }
+
+    if !found {
+        failed.insert(key);
+    }
+    presents.push(shape);
+
+    found
+}
+
+/// Regions that the presents really fit in, found by searching placements.
+pub fn part1(input: &str) -> Answer {
+    let (shapes, regions) = puzzle(input);
+    let shapes: Vec<Vec<Shape>> = shapes.iter().map(orientations).collect();
+
+    regions
+        .iter()
+        .filter(|(width, height, counts)| {
+            let mut presents: Vec<usize> = counts.iter().enumerate().flat_map(|(shape, &count)| vec![shape; count]).collect();
+            let cells: usize = presents.iter().map(|&shape| shapes[shape][0].len()).sum();
+            cells <= width * height && fits(&shapes, *width, *height, &mut presents, &mut vec![0; (width * height).div_ceil(64)], &mut HashSet::new())
+        })
+        .count()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day12::Day12;
+    use crate::generate::{Generator, Regions};
+
+    const TEST: &str = "0:\n###\n##.\n##.\n\n1:\n###\n##.\n.##\n\n2:\n.##\n###\n##.\n\n3:\n##.\n###\n##.\n\n4:\n###\n#..\n###\n\n5:\n###\n.#.\n###\n\n4x4: 0 0 0 0 2 0\n12x5: 1 0 1 0 2 2\n12x5: 1 0 1 0 3 2";
+
+    #[test]
+    fn reference_test() {
+        assert_eq!(part1(TEST), Answer::Int(2));
+
+        // Over 64 cells, which used to overflow a single word of bits.
+        let (shapes, _) = TEST.rsplit_once("\n\n").unwrap();
+        assert_eq!(part1(&format!("{shapes}\n\n9x8: 1 1 1 1 1 1\n9x8: 3 3 3 3 3 3")), Answer::Int(1));
+    }
+
+    // The real solution only counts regions with a 3x3 slot for every
+    // present, which is enough for the puzzle input but can miss regions
+    // where presents have to interlock. It should never count a region that
+    // doesn't fit, though.
+    #[test]
+    fn differential_test() {
+        let input = |case| {
+            let side = 3 + case as usize % 6;
+            Regions { regions: 1 + case as usize % 4, shapes: 6, min_side: 3, max_side: side, fill: 1.2 }.generate_seeded(case)
+        };
+        let never_more = |found: &Answer, expected: &Answer| match (found, expected) {
+            (Answer::Int(found), Answer::Int(expected)) => found <= expected,
+            _ => false,
+        };
+        if let Err(counterexample) = differential_by::<Day12>(1, part1, 500, input, never_more) {
+            panic!("{counterexample}");
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,196 @@
+// Slow but obviously correct solutions, used to check the real ones against
+// random inputs from `generate`. Each one works from the raw input text and
+// does its own parsing, so generator and parser bugs turn up as well.
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+
+use crate::prelude::*;
+use std::panic::{self, AssertUnwindSafe};
+
+/// An input on which a solution disagrees with its reference, shrunk as far
+/// as it would go.
+#[derive(Debug)]
+pub struct Counterexample {
+    pub day: u32,
+    pub part: u32,
+    /// The case that first failed, before shrinking.
+    pub case: u64,
+    pub input: String,
+    pub expected: Answer,
+    /// (variant, answer or panic message) for every solution that disagreed.
+    pub found: Vec<(&'static str, String)>,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "day {} part {} disagrees with the reference on case {}, shrunk to:", self.day, self.part, self.case)?;
+        for line in self.input.lines() {
+            writeln!(f, "    {line}")?;
+        }
+        write!(f, "expected {}", self.expected)?;
+        for (variant, found) in &self.found {
+            write!(f, "\n    {variant}: {found}")?;
+        }
+
+        Ok(())
+    }
+}
+
+enum Verdict {
+    Agree,
+    /// Not a valid input, either for the parser or for the reference.
+    Invalid(String),
+    Disagree(Answer, Vec<(&'static str, String)>),
+}
+
+fn catch<T>(run: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(run)).map_err(|payload| {
+        match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+            (Some(message), _) => format!("panicked: {message}"),
+            (_, Some(message)) => format!("panicked: {message}"),
+            _                  => "panicked".to_string(),
+        }
+    })
+}
+
+/// The main solution for a part, if there is one, followed by its
+/// alternatives.
+fn solvers<S: Solution>(part: u32) -> Vec<(&'static str, DaySolver<S>)> {
+    let main: Option<DaySolver<S>> = if part == 1 { Some(S::part1) } else { S::part2() };
+    let alternatives = S::alternatives().into_iter().filter(|&(p, ..)| p == part);
+
+    main.map(|main| ("main", main))
+        .into_iter()
+        .chain(alternatives.map(|(_, name, solve)| (name, solve)))
+        .collect()
+}
+
+fn compare<S: Solution>(input: &str, part: u32, reference: fn(&str) -> Answer, agree: fn(&Answer, &Answer) -> bool) -> Verdict {
+    let input = input.trim_end_matches('\n');
+    let ctx = Context::<S::Params>::default();
+    let parsed = match S::parse(input, ctx.params()) {
+        Ok(parsed) => parsed,
+        Err(err)   => return Verdict::Invalid(err.to_string()),
+    };
+    let expected = match catch(|| reference(input)) {
+        Ok(expected) => expected,
+        Err(err)     => return Verdict::Invalid(format!("reference {err}")),
+    };
+
+    let found: Vec<(&'static str, String)> = solvers::<S>(part)
+        .into_iter()
+        .filter_map(|(name, solve)| match catch(|| solve(&parsed, &ctx)) {
+            Ok(answer) if agree(&answer, &expected) => None,
+            Ok(answer) => Some((name, answer.to_string())),
+            Err(err)   => Some((name, err)),
+        })
+        .collect();
+
+    if found.is_empty() { Verdict::Agree } else { Verdict::Disagree(expected, found) }
+}
+
+/// Deletes runs of lines, or of comma separated items for a one line input,
+/// for as long as the input still fails.
+fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let separator = if input.contains('\n') { "\n" } else { "," };
+    let mut chunks: Vec<&str> = input.split(separator).collect();
+    let mut size = max(chunks.len() / 2, 1);
+
+    loop {
+        let mut start = 0;
+        let mut removed = false;
+
+        while start + size <= chunks.len() {
+            let candidate = [&chunks[..start], &chunks[start + size..]].concat().join(separator);
+            if !candidate.trim().is_empty() && fails(&candidate) {
+                chunks.drain(start..start + size);
+                removed = true;
+            } else {
+                start += size;
+            }
+        }
+
+        if !removed {
+            if size == 1 {
+                break;
+            }
+            size /= 2;
+        }
+    }
+
+    chunks.join(separator)
+}
+
+/// Checks every solution for a part against the reference on `cases` inputs
+/// from `input(case)`, and shrinks the first one they disagree on. A panic
+/// counts as a disagreement.
+pub fn differential<S: Solution>(
+    part: u32,
+    reference: fn(&str) -> Answer,
+    cases: u64,
+    input: impl Fn(u64) -> String,
+) -> Result<(), Counterexample> {
+    differential_by::<S>(part, reference, cases, input, |found, expected| found == expected)
+}
+
+/// Like `differential`, but with a custom test of whether an answer `agree`s
+/// with the reference's, for solutions that are only meant to be right on
+/// some inputs.
+pub fn differential_by<S: Solution>(
+    part: u32,
+    reference: fn(&str) -> Answer,
+    cases: u64,
+    input: impl Fn(u64) -> String,
+    agree: fn(&Answer, &Answer) -> bool,
+) -> Result<(), Counterexample> {
+    for case in 0..cases {
+        let input = input(case);
+
+        match compare::<S>(&input, part, reference, agree) {
+            Verdict::Agree => {},
+            Verdict::Invalid(err) => panic!("day {} case {case} is not a valid input: {err}\n{input}", S::DAY),
+            Verdict::Disagree(..) => {
+                let fails = |input: &str| matches!(compare::<S>(input, part, reference, agree), Verdict::Disagree(..));
+                let input = shrink(&input, fails);
+                let Verdict::Disagree(expected, found) = compare::<S>(&input, part, reference, agree) else {
+                    unreachable!("shrinking only keeps failing inputs");
+                };
+
+                return Err(Counterexample { day: S::DAY, part, case, input, expected, found });
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs `differential` and panics with the counterexample, for tests.
+#[cfg(test)]
+pub fn assert_agrees<S: Solution>(part: u32, reference: fn(&str) -> Answer, cases: u64, input: impl Fn(u64) -> String) {
+    if let Err(counterexample) = differential::<S>(part, reference, cases, input) {
+        panic!("{counterexample}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_test() {
+        let input = "1\n2\n3\n40\n5\n60\n7";
+        assert_eq!(shrink(input, |input| input.contains("40")), "40");
+        assert_eq!(shrink(input, |input| input.lines().filter(|line| line.len() > 1).count() == 2), "40\n60");
+        assert_eq!(shrink("1,2,30,4", |input| input.contains("30")), "30");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,167 @@
+// Checks every registered solution against the real inputs and a manifest of
+// known answers. Both live under input/, which stays out of git.
+//
+// The manifest has one answer per line, with an optional variant name:
+//
+//     # day part [variant] = answer
+//     8 1 = 123456
+//     4 2 Alternative = 8713
+//
+// A variant without its own line is expected to match the main solution.
+use crate::prelude::*;
+use crate::runner::{Key, answers_path, input_path, registry};
+use std::collections::BTreeMap;
+
+/// Expected answers keyed by (day, part, variant).
+#[derive(Debug, Default)]
+pub struct Manifest {
+    answers: BTreeMap<(u32, u32, Option<String>), Answer>,
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut answers = BTreeMap::new();
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let (key, answer) = parse_line!(line, "{} = {}" => String, String).map_err(|err| err.at_line(idx + 1))?;
+            let fields: Vec<&str> = key.split_whitespace().collect();
+            let fail = |expected| ParseError::new(line, 1, expected, key.as_str()).at_line(idx + 1);
+
+            let (day, part, variant) = match fields[..] {
+                [day, part] => (day, part, None),
+                [day, part, variant] => (day, part, Some(variant.to_string())),
+                _ => return Err(fail("`day part [variant]`")),
+            };
+            let day = day.parse().map_err(|_| fail("a day number"))?;
+            let part = part.parse().map_err(|_| fail("a part number"))?;
+
+            answers.insert((day, part, variant), answer.parse().expect("answers always parse"));
+        }
+
+        Ok(Self { answers })
+    }
+
+    /// Reads the manifest next to the inputs. A missing file is an empty
+    /// manifest.
+    pub fn load() -> Result<Self, ParseError> {
+        match std::fs::read_to_string(answers_path()) {
+            Ok(text) => Self::parse(&text),
+            Err(_)   => Ok(Self::default()),
+        }
+    }
+
+    /// Expected answer, falling back to the main solution's for a variant.
+    pub fn get(&self, (day, part, variant): Key) -> Option<&Answer> {
+        let variant = variant.map(|name| name.to_ascii_lowercase());
+        self.answers
+            .iter()
+            .find(|((d, p, v), _)| {
+                (*d, *p) == (day, part) && v.as_ref().map(|name| name.to_ascii_lowercase()) == variant
+            })
+            .or_else(|| self.answers.get_key_value(&(day, part, None)))
+            .map(|(_, answer)| answer)
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Mismatch { expected: Answer, found: Answer },
+    MissingAnswer(Answer),
+    MissingInput,
+    Failed(String),
+}
+
+impl Check {
+    /// Mismatches and errors are failures; missing inputs and answers are
+    /// only reported.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Check::Mismatch { .. } | Check::Failed(_))
+    }
+}
+
+/// Runs every registered solution whose input is available.
+pub fn check_all(manifest: &Manifest) -> Vec<(Key, Check)> {
+    let mut inputs: HashMap<u32, Option<String>> = HashMap::new();
+
+    registry()
+        .values()
+        .map(|entry| {
+            let input = inputs
+                .entry(entry.day)
+                .or_insert_with(|| std::fs::read_to_string(input_path(entry.day)).ok());
+
+            let check = match input {
+                None => Check::MissingInput,
+                Some(input) => match (entry.run(input), manifest.get(entry.key())) {
+                    (Err(err), _) => Check::Failed(err.to_string()),
+                    (Ok(outcome), None) => Check::MissingAnswer(outcome.answer),
+                    (Ok(outcome), Some(expected)) if outcome.answer == *expected => Check::Pass,
+                    (Ok(outcome), Some(expected)) => Check::Mismatch { expected: expected.clone(), found: outcome.answer },
+                },
+            };
+
+            (entry.key(), check)
+        })
+        .collect()
+}
+
+/// Renders the results as a markdown table.
+pub fn report(results: &[(Key, Check)]) -> String {
+    let mut table = String::new();
+    table.push_str("| Day | Part | Variant     | Status         | Expected             | Found                |\n");
+    table.push_str("|----:|-----:|:------------|:---------------|:---------------------|:---------------------|\n");
+
+    for ((day, part, variant), check) in results {
+        let (status, expected, found) = match check {
+            Check::Pass                         => ("ok", String::new(), String::new()),
+            Check::Mismatch { expected, found } => ("MISMATCH", expected.to_string(), found.to_string()),
+            Check::MissingAnswer(found)         => ("no answer", String::new(), found.to_string()),
+            Check::MissingInput                 => ("no input", String::new(), String::new()),
+            Check::Failed(err)                  => ("FAILED", String::new(), err.lines().next().unwrap_or("").to_string()),
+        };
+        table.push_str(&format!(
+            "| {:>3} | {:>4} | {:<11} | {:<14} | {:<20} | {:<20} |\n",
+            day, part, variant.unwrap_or(""), status, expected, found
+        ));
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_test() {
+        let manifest = Manifest::parse("# comment\n8 1 = 123\n\n4 2 = 43\n4 2 Alternative = 44\n6 2 = EZ\n").unwrap();
+        assert_eq!(manifest.len(), 4);
+        assert_eq!(manifest.get((8, 1, None)), Some(&Answer::Int(123)));
+        assert_eq!(manifest.get((4, 2, Some("alternative"))), Some(&Answer::Int(44)));
+        assert_eq!(manifest.get((9, 2, Some("Compressed"))), None);
+        assert_eq!(manifest.get((6, 2, None)), Some(&Answer::Text("EZ".to_string())));
+
+        let err = Manifest::parse("8 1 = 1\neight 1 = 2").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn fallback_test() {
+        let manifest = Manifest::parse("9 2 = 24").unwrap();
+        assert_eq!(manifest.get((9, 2, Some("Compressed"))), Some(&Answer::Int(24)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,193 @@
+// Registry of every solution, keyed by (day, part, variant), built from each
+// day's `Solution` impl. Used by the CLI and by anything else that wants to
+// run "day 8 part 2 on this string" without knowing the day's types.
+use crate::prelude::*;
+use crate::memory::{AllocStats, measure};
+use crate::params::{Params, from_settings};
+use crate::solution::{Answer, Context, DaySolver, Solution};
+use crate::*;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// (day, part, variant), with `None` for the main solution.
+pub type Key = (u32, u32, Option<&'static str>);
+
+type Run = Box<dyn Fn(&str, &Context, &[(String, String)]) -> Result<Outcome, ParseError> + Send + Sync>;
+type Trace = Box<dyn Fn(&str, &Context, &[(String, String)]) -> Result<Option<String>, ParseError> + Send + Sync>;
+
+/// One registered solution: a day, a part and an optional alternative name.
+pub struct Entry {
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<&'static str>,
+    /// Names of the day's parameters, see `params`.
+    pub params: &'static [&'static str],
+    run: Run,
+    trace: Trace,
+}
+
+/// Answer and per-phase timings from a single run, plus heap use when built
+/// with the `alloc-stats` feature.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub answer: Answer,
+    pub generator: Duration,
+    pub solver: Duration,
+    pub generator_alloc: Option<AllocStats>,
+    pub solver_alloc: Option<AllocStats>,
+}
+
+impl Entry {
+    fn new<S: Solution + 'static>(part: u32, variant: Option<&'static str>, solve: DaySolver<S>) -> Self {
+        let run = move |input: &str, ctx: &Context, settings: &[(String, String)]| {
+            let params = from_settings::<S::Params>(settings).map_err(|err| err.on_day(S::DAY))?;
+            let ctx = ctx.with_params(params);
+
+            let start = Instant::now();
+            let (parsed, generator_alloc) = measure(|| S::parse(input, ctx.params()));
+            let parsed = parsed?;
+            let generated = Instant::now();
+            let (answer, solver_alloc) = measure(|| solve(&parsed, &ctx));
+            let solved = Instant::now();
+
+            Ok(Outcome {
+                answer,
+                generator: generated - start,
+                solver: solved - generated,
+                generator_alloc,
+                solver_alloc,
+            })
+        };
+
+        let trace = |input: &str, ctx: &Context, settings: &[(String, String)]| {
+            let params = from_settings::<S::Params>(settings).map_err(|err| err.on_day(S::DAY))?;
+            let ctx = ctx.with_params(params);
+
+            Ok(S::trace(&S::parse(input, ctx.params())?, &ctx))
+        };
+
+        Self { day: S::DAY, part, variant, params: S::Params::NAMES, run: Box::new(run), trace: Box::new(trace) }
+    }
+
+    /// Parses the input and solves it, timing each phase separately. Trailing
+    /// newlines are dropped first, as cargo-aoc does.
+    pub fn run(&self, input: &str) -> Result<Outcome, ParseError> {
+        self.run_with(input, &Context::default(), &[])
+    }
+
+    /// As `run`, with the given context rather than a silent one, and
+    /// (name, value) settings for any of the day's parameters. Settings the
+    /// day doesn't have are ignored.
+    pub fn run_with(&self, input: &str, ctx: &Context, settings: &[(String, String)]) -> Result<Outcome, ParseError> {
+        (self.run)(input.trim_end_matches('\n'), ctx, settings)
+    }
+
+    /// The day's step by step trace of the input, if it has one. The same
+    /// for every part and variant of a day.
+    pub fn trace_with(&self, input: &str, ctx: &Context, settings: &[(String, String)]) -> Result<Option<String>, ParseError> {
+        (self.trace)(input.trim_end_matches('\n'), ctx, settings)
+    }
+
+    pub fn key(&self) -> Key {
+        (self.day, self.part, self.variant)
+    }
+
+    /// Human readable name, e.g. "Day 4 - Part 2 - Alternative".
+    pub fn label(&self) -> String {
+        match self.variant {
+            Some(variant) => format!("Day {} - Part {} - {}", self.day, self.part, variant),
+            None          => format!("Day {} - Part {}", self.day, self.part),
+        }
+    }
+}
+
+fn register<S: Solution + 'static>(registry: &mut BTreeMap<Key, Entry>) {
+    let mut add = |entry: Entry| {
+        let previous = registry.insert(entry.key(), entry);
+        assert!(previous.is_none(), "day {} registered twice", S::DAY);
+    };
+
+    add(Entry::new::<S>(1, None, S::part1));
+    if let Some(part2) = S::part2() {
+        add(Entry::new::<S>(2, None, part2));
+    }
+    for (part, variant, solve) in S::alternatives() {
+        add(Entry::new::<S>(part, Some(variant), solve));
+    }
+}
+
+/// Every solution in the crate, in (day, part, variant) order with the main
+/// solution before its alternatives. New days need adding here.
+pub fn registry() -> &'static BTreeMap<Key, Entry> {
+    static REGISTRY: OnceLock<BTreeMap<Key, Entry>> = OnceLock::new();
+
+    REGISTRY.get_or_init(|| {
+        let mut registry = BTreeMap::new();
+        register::<day01::Day01>(&mut registry);
+        register::<day02::Day02>(&mut registry);
+        register::<day03::Day03>(&mut registry);
+        register::<day04::Day04>(&mut registry);
+        register::<day05::Day05>(&mut registry);
+        register::<day06::Day06>(&mut registry);
+        register::<day07::Day07>(&mut registry);
+        register::<day08::Day08>(&mut registry);
+        register::<day09::Day09>(&mut registry);
+        register::<day10::Day10>(&mut registry);
+        register::<day11::Day11>(&mut registry);
+        register::<day12::Day12>(&mut registry);
+
+        registry
+    })
+}
+
+/// Looks up a solution. Variant names are matched ignoring case.
+pub fn find(day: u32, part: u32, variant: Option<&str>) -> Option<&'static Entry> {
+    registry()
+        .range((day, part, None)..(day, part + 1, None))
+        .map(|(_, entry)| entry)
+        .find(|entry| match (entry.variant, variant) {
+            (None, None) => true,
+            (Some(name), Some(variant)) => name.eq_ignore_ascii_case(variant),
+            _ => false,
+        })
+}
+
+/// Solves one part of a day on the given input.
+pub fn solve(day: u32, part: u32, variant: Option<&str>, input: &str) -> Result<Answer, Box<dyn std::error::Error>> {
+    let entry = find(day, part, variant).ok_or_else(|| match variant {
+        Some(variant) => format!("no solution for day {day} part {part} ({variant})"),
+        None          => format!("no solution for day {day} part {part}"),
+    })?;
+
+    Ok(entry.run(input)?.answer)
+}
+
+/// Where cargo-aoc keeps a day's input.
+pub fn input_path(day: u32) -> String {
+    format!("input/{YEAR}/day{day}.txt")
+}
+
+/// Where the known answers for the inputs are kept, see `regression`.
+pub fn answers_path() -> String {
+    format!("input/{YEAR}/answers.txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_test() {
+        assert!(find(1, 1, None).is_some());
+        assert!(find(4, 2, Some("alternative")).is_some());
+        assert!(find(4, 2, Some("Rounds")).is_some());
+        assert!(find(4, 2, Some("Missing")).is_none());
+        assert!(find(12, 2, None).is_none());
+
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        assert_eq!(solve(1, 1, None, input).unwrap(), Answer::Int(3));
+        assert!(solve(1, 1, None, "X1").is_err());
+        assert!(solve(12, 2, None, "").is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,163 @@
+// A common interface to every day, so that solutions can be called without
+// going through the aoc-runner attributes. The #[aoc] functions stay as they
+// are, each day just implements `Solution` on top of them.
+use crate::prelude::*;
+use crate::params::Params;
+use crate::progress::{Progress, Silent};
+use std::sync::Arc;
+
+/// A solver for one part of a day.
+pub type Solver<I, P = ()> = fn(&I, &Context<P>) -> Answer;
+
+/// A solver for one part of the day `S`.
+pub type DaySolver<S> = Solver<<S as Solution>::Input, <S as Solution>::Params>;
+
+/// Everything a solver is handed besides its input: the day's parameters
+/// and somewhere to report progress.
+#[derive(Clone)]
+pub struct Context<P = ()> {
+    progress: Arc<dyn Progress>,
+    params: P,
+}
+
+impl Context {
+    pub fn with_progress(progress: impl Progress + 'static) -> Self {
+        Self { progress: Arc::new(progress), params: () }
+    }
+}
+
+impl<P> Context<P> {
+    /// The same progress, with another day's parameters.
+    pub fn with_params<Q>(&self, params: Q) -> Context<Q> {
+        Context { progress: self.progress.clone(), params }
+    }
+
+    pub fn progress(&self) -> &dyn Progress {
+        self.progress.as_ref()
+    }
+
+    pub fn params(&self) -> &P {
+        &self.params
+    }
+}
+
+impl<P: Default> Default for Context<P> {
+    fn default() -> Self {
+        Context::with_progress(Silent).with_params(P::default())
+    }
+}
+
+/// A puzzle answer. Integers that fit in an i64 are always stored as `Int`,
+/// so answers compare equal whichever integer type produced them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    Big(i128),
+    Text(String),
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(value) => Answer::Int(value),
+                        Err(_)    => Answer::Big(value as i128),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+answer_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    /// Anything beyond i128 is kept as text rather than wrapping.
+    fn from(value: u128) -> Self {
+        match i128::try_from(value) {
+            Ok(value) => Answer::from(value),
+            Err(_)    => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl std::str::FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    /// Integers become `Int` or `Big`, anything else is text.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        Ok(match text.parse::<i128>() {
+            Ok(value) => Answer::from(value),
+            Err(_)    => Answer::Text(text.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(value)  => write!(f, "{value}"),
+            Answer::Big(value)  => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// One day's puzzle: how to parse the input and how to solve each part.
+pub trait Solution {
+    const DAY: u32;
+
+    type Input;
+    /// `()` for days without any.
+    type Params: Params;
+
+    fn parse(input: &str, params: &Self::Params) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input, ctx: &Context<Self::Params>) -> Answer;
+
+    /// The part 2 solver, or `None` for days with only one part, like day 12.
+    fn part2() -> Option<DaySolver<Self>> {
+        None
+    }
+
+    /// A step by step account of how the answers come about, for days that
+    /// have one.
+    fn trace(_input: &Self::Input, _ctx: &Context<Self::Params>) -> Option<String> {
+        None
+    }
+
+    /// Other ways of solving a part, as (part, name, solver).
+    fn alternatives() -> Vec<(u32, &'static str, DaySolver<Self>)> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_test() {
+        assert_eq!(Answer::from(42usize), Answer::from(42u128));
+        assert_eq!(Answer::from(-7isize), Answer::Int(-7));
+        assert_eq!(Answer::from(u64::MAX), Answer::Big(u64::MAX as i128));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("EZ").to_string(), "EZ");
+        assert_eq!("6171742720".parse(), Ok(Answer::Int(6171742720)));
+    }
+}
\ No newline at end of file
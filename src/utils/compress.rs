@@ -0,0 +1,115 @@
+use std::ops::{Add, Sub};
+
+/// Coordinate compression along one axis. Each compressed index is a cell
+/// covering an inclusive range of real values. Without gaps every cell is a
+/// single distinct value; with gaps, the values between two neighbours get a
+/// cell of their own so that compressed space keeps the same topology as the
+/// real one (things that were apart stay apart).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedAxis<T> {
+    cells: Vec<(T, T)>,
+}
+
+impl<T> CompressedAxis<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    /// Compresses the distinct values, one cell each.
+    pub fn new<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+
+        Self { cells: values.into_iter().map(|value| (value, value)).collect() }
+    }
+
+    /// Compresses the distinct values, adding a gap cell wherever two
+    /// neighbouring values are not adjacent.
+    pub fn with_gaps<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let one = T::from(1);
+        let distinct = Self::new(values).cells;
+        let mut cells = Vec::with_capacity(distinct.len() * 2);
+
+        for (idx, &(value, _)) in distinct.iter().enumerate() {
+            if idx > 0 {
+                let prev = distinct[idx - 1].0;
+                if value - prev > one {
+                    cells.push((prev + one, value - one));
+                }
+            }
+            cells.push((value, value));
+        }
+
+        Self { cells }
+    }
+
+    /// Number of compressed cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Compressed index of the cell covering a value, found by binary search.
+    /// With gaps, any value between the smallest and largest is covered.
+    pub fn compress(&self, value: T) -> Option<usize> {
+        let idx = self.cells.partition_point(|&(_, hi)| hi < value);
+        match self.cells.get(idx) {
+            Some(&(lo, _)) if lo <= value => Some(idx),
+            _ => None,
+        }
+    }
+
+    /// The first real value covered by a compressed cell.
+    pub fn decompress(&self, idx: usize) -> T {
+        self.cells[idx].0
+    }
+
+    /// The inclusive range of real values covered by a compressed cell.
+    pub fn cell(&self, idx: usize) -> (T, T) {
+        self.cells[idx]
+    }
+
+    /// How many real values a compressed cell stands for.
+    pub fn span(&self, idx: usize) -> T {
+        let (lo, hi) = self.cells[idx];
+        hi - lo + T::from(1)
+    }
+
+    /// Real length covered by the compressed cells from..=to, i.e. the
+    /// weights expanded back. Multiply two axes to get a real area.
+    pub fn length(&self, from: usize, to: usize) -> T {
+        (from + 1..=to).fold(self.span(from), |acc, idx| acc + self.span(idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compress_test() {
+        let axis = CompressedAxis::new([11usize, 2, 7, 9, 7, 2]);
+        assert_eq!(axis.len(), 4);
+        assert_eq!(axis.compress(9), Some(2));
+        assert_eq!(axis.compress(8), None);
+        assert_eq!(axis.decompress(3), 11);
+    }
+
+    #[test]
+    fn gaps_test() {
+        let axis = CompressedAxis::with_gaps([11i64, 2, 7, 8, 3]);
+        // 2, 3, [4..=6], 7, 8, [9..=10], 11
+        assert_eq!(axis.len(), 7);
+        assert_eq!(axis.compress(5), Some(2));
+        assert_eq!(axis.cell(2), (4, 6));
+        assert_eq!(axis.compress(11), Some(6));
+        assert_eq!(axis.compress(12), None);
+        assert_eq!(axis.compress(1), None);
+        assert_eq!(axis.span(5), 2);
+        assert_eq!(axis.length(0, 6), 10);
+        assert_eq!(axis.length(3, 5), 4);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,221 @@
+use crate::prelude::*;
+use std::ops::{Add, BitAnd, BitOr, Sub};
+
+/// Multiset that tallies how many times each item has been seen. Items with a
+/// count of zero are never stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self { counts: HashMap::new() }
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    /// New empty counter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New counter from (item, count) pairs. Counts for repeated items add up.
+    pub fn from_counts<I: IntoIterator<Item = (T, usize)>>(counts: I) -> Self {
+        let mut counter = Self::new();
+        for (item, count) in counts {
+            counter.insert_n(item, count);
+        }
+
+        counter
+    }
+
+    pub fn insert(&mut self, item: T) {
+        self.insert_n(item, 1);
+    }
+
+    pub fn insert_n(&mut self, item: T, count: usize) {
+        if count > 0 {
+            *self.counts.entry(item).or_insert(0) += count;
+        }
+    }
+
+    /// Removes one occurrence of an item. Returns false if it wasn't present.
+    pub fn remove(&mut self, item: &T) -> bool {
+        self.remove_n(item, 1) > 0
+    }
+
+    /// Removes up to n occurrences of an item, returning how many were removed.
+    pub fn remove_n(&mut self, item: &T, count: usize) -> usize {
+        let Some(current) = self.counts.get_mut(item) else {
+            return 0;
+        };
+
+        let removed = min(*current, count);
+        *current -= removed;
+        if *current == 0 {
+            self.counts.remove(item);
+        }
+
+        removed
+    }
+
+    /// Count for an item, zero if it has never been added.
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.counts.contains_key(item)
+    }
+
+    /// Number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Sum of all counts.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Iterates over (item, count) in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+
+    /// All (item, count) pairs from most to least common. Ties come out in no
+    /// particular order.
+    pub fn by_count(&self) -> Vec<(&T, usize)> {
+        let mut counts: Vec<(&T, usize)> = self.iter().collect();
+        counts.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+
+        counts
+    }
+
+    /// The k most common items with their counts.
+    pub fn most_common(&self, k: usize) -> Vec<(&T, usize)> {
+        let mut counts = self.by_count();
+        counts.truncate(k);
+
+        counts
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<T: Eq + Hash> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = std::collections::hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<T: Eq + Hash> Add for Counter<T> {
+    type Output = Self;
+
+    /// Adds counts together.
+    fn add(mut self, rhs: Self) -> Self {
+        for (item, count) in rhs {
+            self.insert_n(item, count);
+        }
+
+        self
+    }
+}
+
+impl<T: Eq + Hash> Sub for Counter<T> {
+    type Output = Self;
+
+    /// Subtracts counts, dropping anything that reaches zero.
+    fn sub(mut self, rhs: Self) -> Self {
+        for (item, count) in rhs {
+            self.remove_n(&item, count);
+        }
+
+        self
+    }
+}
+
+impl<T: Eq + Hash> BitAnd for Counter<T> {
+    type Output = Self;
+
+    /// Intersection: the smaller of each count.
+    fn bitand(self, rhs: Self) -> Self {
+        let counts = self.counts
+            .into_iter()
+            .filter_map(|(item, count)| {
+                let other = rhs.get(&item);
+                if other > 0 { Some((item, min(count, other))) } else { None }
+            })
+            .collect();
+
+        Self { counts }
+    }
+}
+
+impl<T: Eq + Hash> BitOr for Counter<T> {
+    type Output = Self;
+
+    /// Union: the larger of each count.
+    fn bitor(mut self, rhs: Self) -> Self {
+        for (item, count) in rhs {
+            let current = self.counts.entry(item).or_insert(0);
+            *current = max(*current, count);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_test() {
+        let mut counter: Counter<char> = "mississippis".chars().collect();
+        assert_eq!(counter.get(&'s'), 5);
+        assert_eq!(counter.get(&'z'), 0);
+        assert_eq!(counter.len(), 4);
+        assert_eq!(counter.total(), 12);
+        assert_eq!(counter.most_common(2), vec![(&'s', 5), (&'i', 4)]);
+        assert_eq!(counter.by_count().last(), Some(&(&'m', 1)));
+
+        assert!(counter.remove(&'m'));
+        assert!(!counter.remove(&'m'));
+        assert!(!counter.contains(&'m'));
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let a = counter!('a', 'a', 'a', 'b');
+        let b = counter!('a', 'b', 'b', 'c');
+
+        assert_eq!(a.clone() + b.clone(), Counter::from_counts([('a', 4), ('b', 3), ('c', 1)]));
+        assert_eq!(a.clone() - b.clone(), counter!('a'; 2));
+        assert_eq!(a.clone() & b.clone(), counter!('a', 'b'));
+        assert_eq!(a | b, Counter::from_counts([('a', 3), ('b', 2), ('c', 1)]));
+    }
+}
\ No newline at end of file
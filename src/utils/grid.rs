@@ -398,6 +398,20 @@ where
     }
 }
 
+impl<T> Grid<T> {
+    /// Renders the map as text, a line per row, converting each element to a
+    /// character with the given function.
+    pub fn render(&self, to_char: impl Fn(&T) -> char) -> String {
+        let mut map = String::with_capacity((self.width + 1) * self.height);
+        for row in self.entity.chunks(self.width.max(1)) {
+            map.extend(row.iter().map(&to_char));
+            map.push('\n');
+        }
+
+        map
+    }
+}
+
 impl<T> Index<(i32, i32)> for Grid<T> {
     type Output = T;
 
@@ -0,0 +1,215 @@
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// A set of integers kept as sorted, disjoint inclusive ranges. Ranges that
+/// overlap or touch are merged as they go in, so every range is separated
+/// from the next by at least one value outside the set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the set from ranges that are already sorted, disjoint and
+    /// apart, as produced by another set.
+    fn from_sorted(ranges: Vec<(T, T)>) -> Self {
+        Self { ranges }
+    }
+
+    /// Adds every value from `start` to `end`, both included.
+    pub fn insert(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+
+        // Ranges from `first` up to `last` overlap or touch the new one.
+        let one = T::from(1);
+        let first = self.ranges.partition_point(|&(_, other_end)| other_end < start && start - other_end > one);
+        let last = self.ranges.partition_point(|&(other_start, _)| other_start <= end || other_start - end == one);
+
+        if first == last {
+            self.ranges.insert(first, (start, end));
+        } else {
+            let merged = (start.min(self.ranges[first].0), end.max(self.ranges[last - 1].1));
+            self.ranges.splice(first..last, [merged]);
+        }
+    }
+
+    /// The ranges in order, each as (start, end) with both included.
+    pub fn ranges(&self) -> &[(T, T)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many values the set holds, a subtraction per range. This only
+    /// overflows when the set covers every value of `T`.
+    pub fn count(&self) -> T {
+        let one = T::from(1);
+        self.ranges.iter().fold(T::from(0), |count, &(start, end)| count + (end - start) + one)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges.get(idx).is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Whether each of `values` is in the set, walking the values and the
+    /// ranges side by side. The values have to be in ascending order.
+    pub fn contains_sorted(&self, values: &[T]) -> Vec<bool> {
+        debug_assert!(values.is_sorted(), "values must be sorted");
+        let mut ranges = self.ranges.iter().peekable();
+
+        values
+            .iter()
+            .map(|&value| {
+                while ranges.next_if(|&&(_, end)| end < value).is_some() {}
+                ranges.peek().is_some_and(|&&(start, _)| start <= value)
+            })
+            .collect()
+    }
+
+    /// Values in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for &(start, end) in &other.ranges {
+            union.insert(start, end);
+        }
+
+        union
+    }
+
+    /// Values in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut ours, mut theirs) = (self.ranges.iter().peekable(), other.ranges.iter().peekable());
+        let mut ranges = Vec::new();
+
+        while let (Some(&&(start_a, end_a)), Some(&&(start_b, end_b))) = (ours.peek(), theirs.peek()) {
+            let (start, end) = (start_a.max(start_b), end_a.min(end_b));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // Whichever ends first can't meet anything further on.
+            if end_a < end_b {
+                ours.next();
+            } else {
+                theirs.next();
+            }
+        }
+
+        Self::from_sorted(ranges)
+    }
+
+    /// Values from `bound` that are not in the set.
+    pub fn complement(&self, bound: RangeInclusive<T>) -> Self {
+        let (low, high) = (*bound.start(), *bound.end());
+        let one = T::from(1);
+        let mut ranges = Vec::new();
+        let mut next = Some(low);
+
+        for &(start, end) in self.ranges.iter().filter(|&&(start, end)| end >= low && start <= high) {
+            if let Some(from) = next && from < start {
+                ranges.push((from, start - one));
+            }
+            next = (end < high).then(|| end + one);
+        }
+        if let Some(from) = next && from <= high {
+            ranges.push((from, high));
+        }
+
+        Self::from_sorted(ranges)
+    }
+
+    /// Values in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        match (self.ranges.first(), self.ranges.last()) {
+            (Some(&(low, _)), Some(&(_, high))) => self.intersection(&other.complement(low..=high)),
+            _ => Self::new(),
+        }
+    }
+
+    /// The runs of values missing between the first range and the last.
+    pub fn gaps(&self) -> Vec<(T, T)> {
+        let one = T::from(1);
+        self.ranges.windows(2).map(|pair| (pair[0].1 + one, pair[1].0 - one)).collect()
+    }
+}
+
+impl<T> FromIterator<RangeInclusive<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        for range in ranges {
+            set.insert(*range.start(), *range.end());
+        }
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[(u64, u64)]) -> IntervalSet<u64> {
+        ranges.iter().map(|&(start, end)| start..=end).collect()
+    }
+
+    #[test]
+    fn insert_test() {
+        let set = set(&[(10, 14), (3, 5), (16, 20), (12, 18), (6, 6), (30, 29)]);
+        assert_eq!(set.ranges(), [(3, 6), (10, 20)]);
+        assert_eq!(set.count(), 15);
+        assert_eq!(set.gaps(), [(7, 9)]);
+        assert!(set.contains(6) && set.contains(20) && !set.contains(7) && !set.contains(21));
+        assert_eq!(set.contains_sorted(&[0, 3, 7, 9, 10, 15, 20, 25]), [false, true, false, false, true, true, true, false]);
+    }
+
+    #[test]
+    fn operations_test() {
+        let (a, b) = (set(&[(1, 10), (20, 30)]), set(&[(5, 22), (28, 40)]));
+        assert_eq!(a.union(&b).ranges(), [(1, 40)]);
+        assert_eq!(a.intersection(&b).ranges(), [(5, 10), (20, 22), (28, 30)]);
+        assert_eq!(a.difference(&b).ranges(), [(1, 4), (23, 27)]);
+        assert_eq!(b.difference(&a).ranges(), [(11, 19), (31, 40)]);
+        assert_eq!(a.complement(0..=25).ranges(), [(0, 0), (11, 19)]);
+        assert_eq!(a.complement(1..=30).ranges(), [(11, 19)]);
+        assert_eq!(IntervalSet::<u64>::new().complement(3..=4).ranges(), [(3, 4)]);
+
+        // Every operation against a plain set of values.
+        let (a, b) = (set(&[(0, 3), (7, 7), (9, 15), (40, 44)]), set(&[(2, 8), (12, 20), (44, 50)]));
+        let values = |set: &IntervalSet<u64>| (0..=60).filter(|&value| set.contains(value)).collect::<Vec<_>>();
+        let expected = |keep: fn(bool, bool) -> bool| (0..=60).filter(|&value| keep(a.contains(value), b.contains(value))).collect::<Vec<_>>();
+        assert_eq!(values(&a.union(&b)), expected(|a, b| a || b));
+        assert_eq!(values(&a.intersection(&b)), expected(|a, b| a && b));
+        assert_eq!(values(&a.difference(&b)), expected(|a, b| a && !b));
+        assert_eq!(values(&a.complement(0..=60)), expected(|a, _| !a));
+    }
+
+    #[test]
+    fn wide_test() {
+        let set: IntervalSet<u128> = [0..=u128::MAX - 1, 5..=u128::MAX - 2].into_iter().collect();
+        assert_eq!(set.count(), u128::MAX);
+        assert_eq!(set.complement(0..=u128::MAX).ranges(), [(u128::MAX, u128::MAX)]);
+        let set: IntervalSet<u64> = [1..=1_000_000_000_000_000, 2_000_000_000_000_000..=u64::MAX].into_iter().collect();
+        assert_eq!(set.count(), u64::MAX - 999_999_999_999_999);
+        assert_eq!(set.gaps(), [(1_000_000_000_000_001, 1_999_999_999_999_999)]);
+        assert_eq!(set.complement(0..=u64::MAX).count(), 1_000_000_000_000_000);
+    }
+}
\ No newline at end of file
@@ -4,18 +4,17 @@ macro_rules! hashset {
     () => {
         ::std::collections::HashSet::new()
     };
-    
-    // hashset!(element; count) returns a HashSet containing count copies of element
+
+    // hashset!(element; capacity) returns a HashSet seeded with element and
+    // room for capacity elements, e.g. a visited set with its start position
     ($elem:expr; $n:expr) => {
         {
-            let mut set = ::std::collections::HashSet::new();
-            for _ in 0..$n {
-                set.insert($elem);
-            }
+            let mut set = ::std::collections::HashSet::with_capacity($n);
+            set.insert($elem);
             set
         }
     };
-    
+
     // hashset!(elem1, elem2, elem3, ...) returns a HashSet containing all the specified elements.
     ($($x:expr),+ $(,)?) => {
         {
@@ -26,4 +25,70 @@ macro_rules! hashset {
             set
         }
     };
+}
+
+#[macro_export]
+macro_rules! hashmap {
+    // hashmap!() returns a new empty hashmap
+    () => {
+        ::std::collections::HashMap::new()
+    };
+
+    // hashmap!(key1 => value1, key2 => value2, ...) returns a HashMap containing all the pairs.
+    ($($k:expr => $v:expr),+ $(,)?) => {
+        {
+            let mut map = ::std::collections::HashMap::new();
+            $(
+                map.insert($k, $v);
+            )+
+            map
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! btreemap {
+    // btreemap!() returns a new empty btreemap
+    () => {
+        ::std::collections::BTreeMap::new()
+    };
+
+    // btreemap!(key1 => value1, key2 => value2, ...) returns a BTreeMap containing all the pairs.
+    ($($k:expr => $v:expr),+ $(,)?) => {
+        {
+            let mut map = ::std::collections::BTreeMap::new();
+            $(
+                map.insert($k, $v);
+            )+
+            map
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! counter {
+    // counter!() returns a new empty counter
+    () => {
+        $crate::prelude::Counter::new()
+    };
+
+    // counter!(element; count) returns a Counter holding count copies of element
+    ($elem:expr; $n:expr) => {
+        {
+            let mut counter = $crate::prelude::Counter::new();
+            counter.insert_n($elem, $n);
+            counter
+        }
+    };
+
+    // counter!(elem1, elem2, elem1, ...) returns a Counter tallying the elements
+    ($($x:expr),+ $(,)?) => {
+        {
+            let mut counter = $crate::prelude::Counter::new();
+            $(
+                counter.insert($x);
+            )+
+            counter
+        }
+    };
 }
\ No newline at end of file
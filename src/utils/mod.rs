@@ -1,10 +1,22 @@
 #![allow(unused_imports)]
+mod compress;
+mod counter;
 mod dirs;
 mod grid;
+mod interval;
 mod macros;
+mod parse;
 mod point;
+mod polygon;
+mod rect;
 
+pub use compress::*;
+pub use counter::*;
 pub use dirs::*;
 pub use grid::*;
+pub use interval::*;
 pub use macros::*;
-pub use point::*;
\ No newline at end of file
+pub use parse::*;
+pub use point::*;
+pub use polygon::*;
+pub use rect::*;
\ No newline at end of file
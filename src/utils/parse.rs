@@ -0,0 +1,296 @@
+use crate::prelude::*;
+
+/// Input parsing errors, shared by every day's generator. Carries enough to
+/// point at the exact spot in the input that was rejected.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Puzzle day, or 0 if not yet known.
+    pub day: u32,
+    /// 1-based line number, or 0 when parsing a lone string.
+    pub line: usize,
+    /// 1-based column where matching failed.
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    /// The pattern being matched, if the error came from `parse_line!`.
+    pub pattern: Option<String>,
+    /// The offending line, for the excerpt.
+    pub text: String,
+}
+
+impl ParseError {
+    pub fn new(text: &str, column: usize, expected: impl Into<String>, found: impl Into<String>) -> Self {
+        Self {
+            day: 0,
+            line: 0,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+            pattern: None,
+            text: text.to_string(),
+        }
+    }
+
+    /// Error for an input that ended before something required turned up.
+    pub fn missing(expected: impl Into<String>) -> Self {
+        Self::new("", 1, expected, "end of input")
+    }
+
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.pattern = Some(pattern.to_string());
+        self
+    }
+
+    /// Sets the line number the error came from.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Moves the line number down by a number of lines, for errors from a
+    /// section that doesn't start at the top of the input.
+    pub fn shift_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Re-anchors an error from a substring into the line containing it.
+    pub fn within(mut self, text: &str, offset: usize) -> Self {
+        self.column += offset;
+        self.text = text.to_string();
+        self
+    }
+
+    pub fn on_day(mut self, day: u32) -> Self {
+        self.day = day;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    /// Renders the error with a caret under the offending column:
+    ///
+    /// ```text
+    /// day 12, line 3, column 4: expected `: `, found `; 1 0` (pattern `{}x{}: {}`)
+    ///     3 | 12x5; 1 0
+    ///       |    ^
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.day > 0 {
+            write!(f, "day {}, ", self.day)?;
+        }
+        write!(f, "line {}, column {}: expected {}, found `{}`", self.line, self.column, self.expected, self.found)?;
+        if let Some(pattern) = &self.pattern {
+            write!(f, " (pattern `{pattern}`)")?;
+        }
+
+        if !self.text.is_empty() {
+            let gutter = self.line.to_string();
+            let caret = self.text.chars().take(self.column.saturating_sub(1)).count();
+            write!(f, "\n    {gutter} | {}", self.text)?;
+            write!(f, "\n    {} | {}^", " ".repeat(gutter.len()), " ".repeat(caret))?;
+        }
+
+        Ok(())
+    }
+}
+
+// aoc-runner prints generator errors with `{:#?}`, so show the excerpt there
+// as well rather than a field dump.
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Splits the input into blocks separated by blank lines. Each block comes
+/// with the number of lines before it, to pass to `ParseError::shift_lines`.
+/// Works with both LF and CRLF line endings.
+pub fn sections(input: &str) -> Vec<(usize, &str)> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+
+    for (idx, line) in input.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((first, from)) = start.take() {
+                sections.push((first, &input[from..offset]));
+            }
+        } else if start.is_none() {
+            start = Some((idx, offset));
+        }
+        offset += line.len();
+    }
+
+    if let Some((first, from)) = start {
+        sections.push((first, &input[from..]));
+    }
+
+    sections
+}
+
+/// Conversion from a single matched field. On failure, returns a
+/// description of what was expected.
+pub trait FromField: Sized {
+    fn from_field(field: &str) -> Result<Self, String>;
+}
+
+macro_rules! from_field_via_from_str {
+    ($($t:ty),+) => {
+        $(
+            impl FromField for $t {
+                fn from_field(field: &str) -> Result<Self, String> {
+                    field.parse().map_err(|_| format!("{}", stringify!($t)))
+                }
+            }
+        )+
+    };
+}
+
+from_field_via_from_str!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f64, char, bool, String);
+
+/// Lists are split on whitespace if there is any, otherwise on commas, so
+/// both `1 0 2` and `1,0,2` work.
+impl<T: FromField> FromField for Vec<T> {
+    fn from_field(field: &str) -> Result<Self, String> {
+        if field.contains(char::is_whitespace) {
+            field.split_whitespace().map(T::from_field).collect()
+        } else if field.is_empty() {
+            Ok(Vec::new())
+        } else {
+            field.split(',').map(T::from_field).collect()
+        }
+    }
+}
+
+/// Matches text against a pattern of literals and `{}` placeholders, and
+/// returns each placeholder's text with its byte offset. A placeholder runs
+/// up to the next occurrence of the literal that follows it, or to the end of
+/// the text. Two placeholders with nothing between them split after a single
+/// character, as in `{}{}` for `L68`.
+pub fn scan<'a>(text: &'a str, pattern: &str) -> Result<Vec<(usize, &'a str)>, ParseError> {
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    let mut fields = Vec::with_capacity(literals.len() - 1);
+    let fail = |pos: usize, expected: String| {
+        Err(ParseError::new(text, pos + 1, expected, &text[pos..]).with_pattern(pattern))
+    };
+
+    if !text.starts_with(literals[0]) {
+        return fail(0, format!("`{}`", literals[0]));
+    }
+    let mut pos = literals[0].len();
+
+    for (idx, literal) in literals.iter().enumerate().skip(1) {
+        let rest = &text[pos..];
+        let is_last = idx == literals.len() - 1;
+
+        let len = if literal.is_empty() && is_last {
+            rest.len()
+        } else if literal.is_empty() {
+            rest.chars().next().map_or(0, char::len_utf8)
+        } else {
+            match rest.find(literal) {
+                Some(len) => len,
+                None => return fail(pos, format!("`{literal}`")),
+            }
+        };
+
+        if len == 0 {
+            return fail(pos, "a value".to_string());
+        }
+
+        fields.push((pos, &rest[..len]));
+        pos += len + literal.len();
+
+        if is_last && pos != text.len() {
+            return fail(pos, "end of line".to_string());
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Parses one string against a pattern into a tuple of typed fields, e.g.
+/// `parse_line!(line, "{}x{}: {}" => usize, usize, Vec<usize>)`. Returns a
+/// `Result` whose error points at the field that failed.
+#[macro_export]
+macro_rules! parse_line {
+    ($line:expr, $pattern:expr => $($t:ty),+ $(,)?) => {
+        {
+            let line: &str = $line;
+            let pattern: &str = $pattern;
+            $crate::prelude::scan(line, pattern).and_then(|fields| {
+                let mut fields = fields.into_iter();
+                Ok(($(
+                    {
+                        let (offset, field) = fields.next().expect("fewer placeholders than types");
+                        <$t as $crate::prelude::FromField>::from_field(field)
+                            .map_err(|expected| {
+                                $crate::prelude::ParseError::new(line, offset + 1, expected, field).with_pattern(pattern)
+                            })?
+                    },
+                )+))
+            })
+        }
+    };
+}
+
+/// Parses every line of the input with `parse_line!`, ignoring trailing
+/// whitespace, and collects the tuples. Errors carry the line number.
+#[macro_export]
+macro_rules! parse_lines {
+    ($input:expr, $pattern:expr => $($t:ty),+ $(,)?) => {
+        {
+            let input: &str = $input;
+            input
+                .lines()
+                .enumerate()
+                .map(|(idx, line)| {
+                    $crate::parse_line!(line.trim_end(), $pattern => $($t),+)
+                        .map_err(|err| err.at_line(idx + 1))
+                })
+                .collect::<Result<Vec<_>, $crate::prelude::ParseError>>()
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_line_test() {
+        assert_eq!(parse_line!("12x5: 1 0 1 0 2 2", "{}x{}: {}" => usize, usize, Vec<usize>), Ok((12, 5, vec![1, 0, 1, 0, 2, 2])));
+        assert_eq!(parse_line!("L68", "{}{}" => char, isize), Ok(('L', 68)));
+        assert_eq!(parse_line!("{3,5,4,7}", "{{}}" => Vec<usize>), Ok((vec![3, 5, 4, 7],)));
+        assert_eq!(parse_line!("aaa: you hhh", "{}: {}" => String, Vec<String>), Ok(("aaa".to_string(), vec!["you".to_string(), "hhh".to_string()])));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let err = parse_line!("12x5; 1 0", "{}x{}: {}" => usize, usize, Vec<usize>).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str(), err.found.as_str()), (4, "`: `", "5; 1 0"));
+        assert_eq!(err.pattern.as_deref(), Some("{}x{}: {}"));
+
+        let err = parse_line!("12xfive: 1 0", "{}x{}: {}" => usize, usize, Vec<usize>).unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (4, "five"));
+
+        let err = parse_lines!("1-2\n3-4\n5_6", "{}-{}" => usize, usize).unwrap_err().on_day(5);
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(
+            err.to_string(),
+            "day 5, line 3, column 1: expected `-`, found `5_6` (pattern `{}-{}`)\n    3 | 5_6\n      | ^"
+        );
+    }
+
+    #[test]
+    fn sections_test() {
+        let input = "0:\r\n##\r\n\r\n1:\r\n#.\r\n\r\n\r\n4x4: 1 2";
+        let parts = sections(input);
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[1], (3, "1:\r\n#.\r\n"));
+        assert_eq!(parts[2], (7, "4x4: 1 2"));
+    }
+}
\ No newline at end of file
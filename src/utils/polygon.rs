@@ -0,0 +1,294 @@
+use crate::prelude::*;
+
+/// Polygon errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonError {
+    /// Fewer than three vertices were given.
+    TooFewVertices,
+    /// The edge starting at this vertex index is neither horizontal nor vertical.
+    NotAxisAligned(usize),
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PolygonError::TooFewVertices => write!(f, "a polygon needs at least three vertices"),
+            PolygonError::NotAxisAligned(idx) => write!(f, "edge from vertex {idx} is not axis-aligned"),
+        }
+    }
+}
+
+impl std::error::Error for PolygonError {}
+
+/// Winding direction of the vertex list. Directions are given for the usual
+/// maths axes (y up); on a y-down puzzle grid they appear mirrored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Degenerate,
+}
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// A closed simple polygon on integer coordinates. The last vertex connects
+/// back to the first, so the closing vertex should not be repeated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    pub vertices: Vec<(i64, i64)>,
+}
+
+impl Polygon {
+    /// New polygon from a list of vertices
+    pub fn new(vertices: Vec<(i64, i64)>) -> Result<Self, PolygonError> {
+        if vertices.len() < 3 {
+            return Err(PolygonError::TooFewVertices);
+        }
+
+        Ok(Self { vertices })
+    }
+
+    /// New polygon where every edge must be horizontal or vertical, e.g. dig
+    /// plans or tile loops.
+    pub fn new_rectilinear(vertices: Vec<(i64, i64)>) -> Result<Self, PolygonError> {
+        let polygon = Self::new(vertices)?;
+        polygon.validate_rectilinear()?;
+
+        Ok(polygon)
+    }
+
+    /// Iterates over the edges as (from, to) pairs, including the closing edge.
+    pub fn edges(&self) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+        let len = self.vertices.len();
+        (0..len).map(move |idx| (self.vertices[idx], self.vertices[(idx + 1) % len]))
+    }
+
+    /// Checks that consecutive vertices share either an x or a y coordinate.
+    pub fn validate_rectilinear(&self) -> Result<(), PolygonError> {
+        for (idx, (a, b)) in self.edges().enumerate() {
+            if a.0 != b.0 && a.1 != b.1 {
+                return Err(PolygonError::NotAxisAligned(idx));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_rectilinear(&self) -> bool {
+        self.validate_rectilinear().is_ok()
+    }
+
+    /// Shoelace formula. Returns twice the signed area so that it stays an
+    /// integer; positive for counter-clockwise winding.
+    pub fn signed_double_area(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+            .sum()
+    }
+
+    /// Area enclosed by the vertices (not the number of cells covered).
+    pub fn area(&self) -> f64 {
+        self.signed_double_area().abs() as f64 / 2.0
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        match self.signed_double_area().cmp(&0) {
+            Ordering::Greater => Orientation::CounterClockwise,
+            Ordering::Less    => Orientation::Clockwise,
+            Ordering::Equal   => Orientation::Degenerate,
+        }
+    }
+
+    /// Euclidean length of the boundary.
+    pub fn perimeter(&self) -> f64 {
+        self.edges()
+            .map(|(a, b)| (((b.0 - a.0).pow(2) + (b.1 - a.1).pow(2)) as f64).sqrt())
+            .sum()
+    }
+
+    /// Number of lattice points on the boundary. For a rectilinear polygon
+    /// this is also its perimeter length.
+    pub fn boundary_points(&self) -> i64 {
+        fn gcd(a: i64, b: i64) -> i64 {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+
+        self.edges()
+            .map(|(a, b)| gcd((b.0 - a.0).abs(), (b.1 - a.1).abs()))
+            .sum()
+    }
+
+    /// Number of lattice points strictly inside, from Pick's theorem:
+    /// A = I + B/2 - 1.
+    pub fn interior_points(&self) -> i64 {
+        (self.signed_double_area().abs() - self.boundary_points() + 2) / 2
+    }
+
+    /// Number of lattice points inside or on the boundary. When each point is
+    /// a grid cell, this is the number of cells the loop encloses.
+    pub fn lattice_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Checks whether a point lies on any edge.
+    pub fn on_boundary(&self, (x, y): (i64, i64)) -> bool {
+        self.edges().any(|(a, b)| {
+            let cross = (b.0 - a.0) * (y - a.1) - (b.1 - a.1) * (x - a.0);
+            cross == 0
+                && x >= min(a.0, b.0) && x <= max(a.0, b.0)
+                && y >= min(a.1, b.1) && y <= max(a.1, b.1)
+        })
+    }
+
+    /// General point-in-polygon test by ray casting. Works for any simple
+    /// polygon.
+    pub fn locate(&self, point: (i64, i64)) -> Location {
+        if self.on_boundary(point) {
+            return Location::Boundary;
+        }
+
+        let (x, y) = point;
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            // Half-open rule so a ray through a vertex is only counted once.
+            if (a.1 > y) != (b.1 > y) {
+                // x-intercept compared without division: x < a.x + (y - a.y) * dx / dy
+                let lhs = (x - a.0) * (b.1 - a.1);
+                let rhs = (y - a.1) * (b.0 - a.0);
+                if (b.1 > a.1 && lhs < rhs) || (b.1 < a.1 && lhs > rhs) {
+                    inside = !inside;
+                }
+            }
+        }
+
+        if inside { Location::Inside } else { Location::Outside }
+    }
+
+    /// Point-in-polygon test for rectilinear polygons. Only the vertical
+    /// edges can cross a horizontal ray, so this avoids any multiplication.
+    pub fn locate_rectilinear(&self, point: (i64, i64)) -> Location {
+        let (x, y) = point;
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            if a.0 == b.0 {
+                if x == a.0 && y >= min(a.1, b.1) && y <= max(a.1, b.1) {
+                    return Location::Boundary;
+                }
+                if a.0 > x && (a.1 > y) != (b.1 > y) {
+                    inside = !inside;
+                }
+            } else if y == a.1 && x >= min(a.0, b.0) && x <= max(a.0, b.0) {
+                return Location::Boundary;
+            }
+        }
+
+        if inside { Location::Inside } else { Location::Outside }
+    }
+
+    /// Checks whether a point lies inside or on the boundary.
+    pub fn contains(&self, point: (i64, i64)) -> bool {
+        self.locate(point) != Location::Outside
+    }
+
+    /// Checks whether any edge touches the given rectangle. Pass a rectangle's
+    /// `interior()` to ask whether the boundary cuts through it; if it does
+    /// not, the interior lies wholly inside or wholly outside the polygon.
+    ///
+    /// The boundary is treated as a line. Where the vertices are grid tiles
+    /// and the question is whether every tile in a rectangle is inside, as
+    /// for day 9's tile loop, this is not enough: two edges one apart leave a
+    /// notch with no tiles in it that still cuts through the interior. See
+    /// `day09::tests::narrow_notch_test`.
+    pub fn boundary_intersects(&self, rect: &Rect) -> bool {
+        if rect.is_empty() {
+            return false;
+        }
+
+        let corners = [
+            rect.min,
+            (rect.max.0, rect.min.1),
+            rect.max,
+            (rect.min.0, rect.max.1),
+        ];
+
+        self.edges().any(|(a, b)| {
+            let bbox = Rect::from_corners(a, b);
+            if !bbox.intersects(rect) {
+                return false;
+            }
+
+            // Separating axis along the edge normal: the edge misses the
+            // rectangle only if every corner is strictly on the same side.
+            let sides: [i64; 4] = corners.map(|c| ((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)).signum());
+            !(sides.iter().all(|&side| side > 0) || sides.iter().all(|&side| side < 0))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The tile loop from the day 9 example.
+    fn example() -> Polygon {
+        Polygon::new_rectilinear(vec![(7, 1), (11, 1), (11, 7), (9, 7), (9, 5), (2, 5), (2, 3), (7, 3)]).unwrap()
+    }
+
+    #[test]
+    fn area_test() {
+        let poly = example();
+        assert_eq!(poly.area(), 30.0);
+        assert_eq!(poly.boundary_points(), 30);
+        assert_eq!(poly.interior_points(), 16);
+        assert_eq!(poly.lattice_points(), 46);
+        assert_eq!(poly.orientation(), Orientation::CounterClockwise);
+    }
+
+    #[test]
+    fn validate_test() {
+        assert_eq!(Polygon::new(vec![(0, 0), (1, 1)]), Err(PolygonError::TooFewVertices));
+        assert_eq!(Polygon::new_rectilinear(vec![(0, 0), (4, 0), (4, 4)]), Err(PolygonError::NotAxisAligned(2)));
+    }
+
+    #[test]
+    fn locate_test() {
+        let poly = example();
+        for y in 0..=8 {
+            for x in 0..=12 {
+                assert_eq!(poly.locate((x, y)), poly.locate_rectilinear((x, y)), "({x}, {y})");
+            }
+        }
+        assert_eq!(poly.locate((8, 2)), Location::Inside);
+        assert_eq!(poly.locate((7, 2)), Location::Boundary);
+        assert_eq!(poly.locate((4, 2)), Location::Outside);
+        assert_eq!(poly.locate((10, 6)), Location::Inside);
+        assert_eq!(poly.locate((3, 6)), Location::Outside);
+
+        let triangle = Polygon::new(vec![(0, 0), (6, 0), (0, 6)]).unwrap();
+        assert_eq!(triangle.locate((1, 1)), Location::Inside);
+        assert_eq!(triangle.locate((3, 3)), Location::Boundary);
+        assert_eq!(triangle.locate((4, 4)), Location::Outside);
+        assert_eq!(triangle.interior_points(), 10);
+    }
+
+    #[test]
+    fn boundary_intersects_test() {
+        let poly = example();
+        assert!(!poly.boundary_intersects(&Rect::from_corners((9, 5), (2, 3)).interior()));
+        assert!(poly.boundary_intersects(&Rect::from_corners((11, 1), (2, 5)).interior()));
+        assert!(!poly.boundary_intersects(&Rect::inclusive((3, 0), (5, 1))));
+
+        let triangle = Polygon::new(vec![(0, 0), (6, 0), (0, 6)]).unwrap();
+        assert!(triangle.boundary_intersects(&Rect::inclusive((3, 3), (5, 5))));
+        assert!(!triangle.boundary_intersects(&Rect::inclusive((4, 4), (5, 5))));
+        assert!(!triangle.boundary_intersects(&Rect::inclusive((1, 1), (2, 2))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,274 @@
+use crate::prelude::*;
+
+/// Axis-aligned rectangle with inclusive integer bounds. A rectangle whose
+/// max is below its min on either axis is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: (i64, i64),
+    pub max: (i64, i64),
+}
+
+impl Rect {
+    /// New rectangle covering min..=max
+    pub fn inclusive(min: (i64, i64), max: (i64, i64)) -> Self {
+        Self { min, max }
+    }
+
+    /// New rectangle covering min..end
+    pub fn exclusive(min: (i64, i64), end: (i64, i64)) -> Self {
+        Self { min, max: (end.0 - 1, end.1 - 1) }
+    }
+
+    /// New rectangle spanning two opposite corners given in any order.
+    pub fn from_corners(a: (i64, i64), b: (i64, i64)) -> Self {
+        Self {
+            min: (min(a.0, b.0), min(a.1, b.1)),
+            max: (max(a.0, b.0), max(a.1, b.1)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max.0 < self.min.0 || self.max.1 < self.min.1
+    }
+
+    pub fn width(&self) -> i64 {
+        max(self.max.0 - self.min.0 + 1, 0)
+    }
+
+    pub fn height(&self) -> i64 {
+        max(self.max.1 - self.min.1 + 1, 0)
+    }
+
+    /// Number of cells covered.
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, (x, y): (i64, i64)) -> bool {
+        x >= self.min.0 && x <= self.max.0 && y >= self.min.1 && y <= self.max.1
+    }
+
+    /// Checks whether the other rectangle lies entirely within this one.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        other.is_empty() || (self.contains(other.min) && self.contains(other.max))
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Overlapping region, if there is one.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect {
+            min: (max(self.min.0, other.min.0), max(self.min.1, other.min.1)),
+            max: (min(self.max.0, other.max.0), min(self.max.1, other.max.1)),
+        };
+
+        if rect.is_empty() { None } else { Some(rect) }
+    }
+
+    /// Smallest rectangle covering both.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return *other;
+        } else if other.is_empty() {
+            return *self;
+        }
+
+        Rect {
+            min: (min(self.min.0, other.min.0), min(self.min.1, other.min.1)),
+            max: (max(self.max.0, other.max.0), max(self.max.1, other.max.1)),
+        }
+    }
+
+    /// Removes the other rectangle from this one, leaving up to four disjoint
+    /// pieces: full-width bands above and below, then the left and right
+    /// remainders between them.
+    pub fn subtract(&self, other: &Rect) -> Vec<Rect> {
+        let Some(cut) = self.intersection(other) else {
+            return if self.is_empty() { Vec::new() } else { vec![*self] };
+        };
+
+        let pieces = [
+            Rect::inclusive(self.min, (self.max.0, cut.min.1 - 1)),
+            Rect::inclusive((self.min.0, cut.max.1 + 1), self.max),
+            Rect::inclusive((self.min.0, cut.min.1), (cut.min.0 - 1, cut.max.1)),
+            Rect::inclusive((cut.max.0 + 1, cut.min.1), (self.max.0, cut.max.1)),
+        ];
+
+        pieces.into_iter().filter(|rect| !rect.is_empty()).collect()
+    }
+
+    /// The rectangle shrunk by one on every side, i.e. the cells that do not
+    /// touch the edge. May be empty.
+    pub fn interior(&self) -> Rect {
+        Rect {
+            min: (self.min.0 + 1, self.min.1 + 1),
+            max: (self.max.0 - 1, self.max.1 - 1),
+        }
+    }
+
+    /// Iterates over every contained point, row by row.
+    pub fn points(&self) -> impl Iterator<Item = (i64, i64)> + use<> {
+        let (min, max) = (self.min, self.max);
+        (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| (x, y)))
+    }
+}
+
+/// Axis-aligned box with inclusive integer bounds, the 3D variant of `Rect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: (i64, i64, i64),
+    pub max: (i64, i64, i64),
+}
+
+impl Cuboid {
+    /// New box covering min..=max
+    pub fn inclusive(min: (i64, i64, i64), max: (i64, i64, i64)) -> Self {
+        Self { min, max }
+    }
+
+    /// New box covering min..end
+    pub fn exclusive(min: (i64, i64, i64), end: (i64, i64, i64)) -> Self {
+        Self { min, max: (end.0 - 1, end.1 - 1, end.2 - 1) }
+    }
+
+    /// New box spanning two opposite corners given in any order.
+    pub fn from_corners(a: (i64, i64, i64), b: (i64, i64, i64)) -> Self {
+        Self {
+            min: (min(a.0, b.0), min(a.1, b.1), min(a.2, b.2)),
+            max: (max(a.0, b.0), max(a.1, b.1), max(a.2, b.2)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max.0 < self.min.0 || self.max.1 < self.min.1 || self.max.2 < self.min.2
+    }
+
+    /// Side lengths along x, y and z.
+    pub fn dims(&self) -> (i64, i64, i64) {
+        (
+            max(self.max.0 - self.min.0 + 1, 0),
+            max(self.max.1 - self.min.1 + 1, 0),
+            max(self.max.2 - self.min.2 + 1, 0),
+        )
+    }
+
+    /// Number of cells covered.
+    pub fn volume(&self) -> i64 {
+        let (dx, dy, dz) = self.dims();
+        dx * dy * dz
+    }
+
+    pub fn contains(&self, (x, y, z): (i64, i64, i64)) -> bool {
+        x >= self.min.0 && x <= self.max.0 &&
+        y >= self.min.1 && y <= self.max.1 &&
+        z >= self.min.2 && z <= self.max.2
+    }
+
+    /// Checks whether the other box lies entirely within this one.
+    pub fn contains_cuboid(&self, other: &Cuboid) -> bool {
+        other.is_empty() || (self.contains(other.min) && self.contains(other.max))
+    }
+
+    pub fn intersects(&self, other: &Cuboid) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Overlapping region, if there is one.
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let cuboid = Cuboid {
+            min: (max(self.min.0, other.min.0), max(self.min.1, other.min.1), max(self.min.2, other.min.2)),
+            max: (min(self.max.0, other.max.0), min(self.max.1, other.max.1), min(self.max.2, other.max.2)),
+        };
+
+        if cuboid.is_empty() { None } else { Some(cuboid) }
+    }
+
+    /// Smallest box covering both.
+    pub fn union(&self, other: &Cuboid) -> Cuboid {
+        if self.is_empty() {
+            return *other;
+        } else if other.is_empty() {
+            return *self;
+        }
+
+        Cuboid {
+            min: (min(self.min.0, other.min.0), min(self.min.1, other.min.1), min(self.min.2, other.min.2)),
+            max: (max(self.max.0, other.max.0), max(self.max.1, other.max.1), max(self.max.2, other.max.2)),
+        }
+    }
+
+    /// Removes the other box from this one, leaving up to six disjoint
+    /// pieces: slabs below and above in z, then in y, then in x.
+    pub fn subtract(&self, other: &Cuboid) -> Vec<Cuboid> {
+        let Some(cut) = self.intersection(other) else {
+            return if self.is_empty() { Vec::new() } else { vec![*self] };
+        };
+
+        let (lo, hi) = (self.min, self.max);
+        let pieces = [
+            Cuboid::inclusive(lo, (hi.0, hi.1, cut.min.2 - 1)),
+            Cuboid::inclusive((lo.0, lo.1, cut.max.2 + 1), hi),
+            Cuboid::inclusive((lo.0, lo.1, cut.min.2), (hi.0, cut.min.1 - 1, cut.max.2)),
+            Cuboid::inclusive((lo.0, cut.max.1 + 1, cut.min.2), (hi.0, hi.1, cut.max.2)),
+            Cuboid::inclusive((lo.0, cut.min.1, cut.min.2), (cut.min.0 - 1, cut.max.1, cut.max.2)),
+            Cuboid::inclusive((cut.max.0 + 1, cut.min.1, cut.min.2), (hi.0, cut.max.1, cut.max.2)),
+        ];
+
+        pieces.into_iter().filter(|cuboid| !cuboid.is_empty()).collect()
+    }
+
+    /// Iterates over every contained point, x fastest then y then z.
+    pub fn points(&self) -> impl Iterator<Item = (i64, i64, i64)> + use<> {
+        let (min, max) = (self.min, self.max);
+        (min.2..=max.2).flat_map(move |z| {
+            (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| (x, y, z)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rect_test() {
+        let a = Rect::from_corners((9, 5), (2, 3));
+        assert_eq!(a, Rect::inclusive((2, 3), (9, 5)));
+        assert_eq!(a, Rect::exclusive((2, 3), (10, 6)));
+        assert_eq!(a.area(), 24);
+        assert_eq!(a.points().count(), 24);
+        assert_eq!(a.interior(), Rect::inclusive((3, 4), (8, 4)));
+        assert!(Rect::inclusive((0, 0), (1, 1)).interior().is_empty());
+
+        let b = Rect::inclusive((5, 0), (12, 4));
+        assert_eq!(a.intersection(&b), Some(Rect::inclusive((5, 3), (9, 4))));
+        assert_eq!(a.union(&b), Rect::inclusive((2, 0), (12, 5)));
+        assert!(a.union(&b).contains_rect(&a));
+        assert_eq!(a.intersection(&Rect::inclusive((10, 0), (12, 9))), None);
+    }
+
+    #[test]
+    fn subtract_test() {
+        let a = Rect::inclusive((0, 0), (9, 9));
+        let b = Rect::inclusive((3, 4), (5, 12));
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces.iter().map(Rect::area).sum::<i64>(), a.area() - 18);
+        for point in a.points() {
+            let covering = pieces.iter().filter(|piece| piece.contains(point)).count();
+            assert_eq!(covering, if b.contains(point) { 0 } else { 1 });
+        }
+
+        let c = Cuboid::inclusive((0, 0, 0), (4, 4, 4));
+        let d = Cuboid::inclusive((1, 1, 1), (2, 2, 2));
+        let pieces = c.subtract(&d);
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<i64>(), 125 - 8);
+        for point in c.points() {
+            let covering = pieces.iter().filter(|piece| piece.contains(point)).count();
+            assert_eq!(covering, if d.contains(point) { 0 } else { 1 });
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+// Known-answer checks against the real puzzle inputs. Inputs and the answers
+// manifest are kept in input/2025, outside of git, so this is skipped on a
+// fresh checkout.
+use aoc_2025::regression::{Check, Manifest, check_all, report};
+use aoc_2025::runner::answers_path;
+
+#[test]
+fn known_answers() {
+    let manifest = Manifest::load().unwrap_or_else(|err| panic!("{}: {err}", answers_path()));
+    let results = check_all(&manifest);
+
+    if results.iter().all(|(_, check)| *check == Check::MissingInput) {
+        eprintln!("skipping: no puzzle inputs found in input/2025");
+        return;
+    }
+
+    eprintln!("{}", report(&results));
+
+    let failures = results.iter().filter(|(_, check)| check.is_failure()).count();
+    assert_eq!(failures, 0, "{failures} solution(s) disagree with {} or failed to run", answers_path());
+}